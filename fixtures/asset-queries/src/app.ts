@@ -0,0 +1,8 @@
+import logoUrl from './assets/logo.svg?url';
+import closeIcon from './assets/icons.svg#close';
+import changelog from '../CHANGELOG.md?raw';
+import ResizeWorker from './resize.ts?worker';
+import snippetSource from './snippet.ts?raw';
+
+export const scale = 2;
+export const app = { logoUrl, closeIcon, changelog, ResizeWorker, snippetSource };
@@ -0,0 +1,5 @@
+import { scale } from './app';
+
+self.onmessage = (event: MessageEvent<number>) => {
+  self.postMessage(event.data * scale);
+};
@@ -0,0 +1,5 @@
+import { y } from '@admin/y';
+
+export function x() {
+  return y();
+}
@@ -0,0 +1,5 @@
+import { x } from '@admin/x';
+
+export function y() {
+  return x;
+}
@@ -0,0 +1,10 @@
+const path = require('path');
+
+module.exports = {
+  entry: './src/x.js',
+  resolve: {
+    alias: {
+      '@admin': path.resolve(__dirname, 'src'),
+    },
+  },
+};
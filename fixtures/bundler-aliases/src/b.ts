@@ -0,0 +1,3 @@
+import { c } from '~shared/c';
+
+export const b = () => c();
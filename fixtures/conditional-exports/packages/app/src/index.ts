@@ -0,0 +1,7 @@
+import { bootstrap } from '@acme/core';
+
+export function renderApp() {
+  return 'app';
+}
+
+export const start = () => bootstrap();
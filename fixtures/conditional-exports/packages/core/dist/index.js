@@ -0,0 +1,3 @@
+export function bootstrap() {
+  return 'prebuilt';
+}
@@ -0,0 +1,5 @@
+import { renderApp } from '@acme/app';
+
+export function bootstrap() {
+  return renderApp();
+}
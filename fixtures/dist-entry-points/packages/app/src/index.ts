@@ -0,0 +1,7 @@
+import { Button } from '@acme/ui';
+
+export const theme = { primary: '#0af' };
+
+export function App() {
+  return Button('Start');
+}
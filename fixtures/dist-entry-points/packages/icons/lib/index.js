@@ -0,0 +1,7 @@
+"use strict";
+Object.defineProperty(exports, "__esModule", { value: true });
+exports.icon = icon;
+function icon(name) {
+  return `${name}@24`;
+}
+//# sourceMappingURL=index.js.map
@@ -0,0 +1,5 @@
+import { WIDGET_SIZE } from '@acme/widgets';
+
+export function icon(name: string) {
+  return `${name}@${WIDGET_SIZE}`;
+}
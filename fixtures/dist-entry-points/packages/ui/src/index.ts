@@ -0,0 +1,5 @@
+import { theme } from '@acme/app';
+
+export function Button(label: string) {
+  return `<button style="color: ${theme.primary}">${label}</button>`;
+}
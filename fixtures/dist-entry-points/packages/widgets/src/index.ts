@@ -0,0 +1,7 @@
+import { icon } from '@acme/icons';
+
+export const WIDGET_SIZE = 24;
+
+export function Widget() {
+  return icon('star');
+}
@@ -0,0 +1,3 @@
+module.exports = function dispatch(name, payload) {
+  return require('./handlers/' + name + '.js')(payload);
+};
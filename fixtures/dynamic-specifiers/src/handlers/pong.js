@@ -0,0 +1 @@
+module.exports = (payload) => payload;
@@ -0,0 +1,5 @@
+export const fallback = 'en';
+
+export function loadLocale(lang: string) {
+  return import(`./locales/${lang}.ts`);
+}
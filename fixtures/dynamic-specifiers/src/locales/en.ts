@@ -0,0 +1,3 @@
+import { fallback } from '../i18n';
+
+export default { lang: fallback, hello: 'Hello' };
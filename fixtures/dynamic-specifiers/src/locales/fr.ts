@@ -0,0 +1 @@
+export default { lang: 'fr', hello: 'Bonjour' };
@@ -0,0 +1,7 @@
+import { Derived } from './derived';
+
+export class Base {
+  static create(): Base {
+    return new Derived();
+  }
+}
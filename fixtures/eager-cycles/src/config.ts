@@ -0,0 +1,3 @@
+import { defaults } from './defaults';
+
+export const config = { ...defaults, debug: false };
@@ -0,0 +1,4 @@
+import { config } from './config';
+
+export const defaults = { retries: 3 };
+export const snapshot = Object.freeze(config);
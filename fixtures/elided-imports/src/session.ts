@@ -0,0 +1,8 @@
+// `User` is only used as a type, so TypeScript removes this import when compiling
+import { User } from './user';
+
+export class Session {
+  owner?: User;
+
+  constructor(public token: string) {}
+}
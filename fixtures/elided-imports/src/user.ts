@@ -0,0 +1,10 @@
+import { Session } from './session';
+
+export interface User {
+  id: string;
+  session: Session;
+}
+
+export function createUser(id: string): User {
+  return { id, session: new Session(id) };
+}
@@ -0,0 +1,3 @@
+const context = require.context('./modules', true, /\.js$/);
+
+module.exports = context.keys().map(context);
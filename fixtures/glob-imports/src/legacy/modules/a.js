@@ -0,0 +1,3 @@
+const registry = require('../index');
+
+module.exports = () => registry.length;
@@ -0,0 +1,4 @@
+// Not matched by the require.context filter
+import registry from '../index';
+
+export default registry;
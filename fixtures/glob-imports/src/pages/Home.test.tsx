@@ -0,0 +1,3 @@
+import { routes } from '../router';
+
+test('routes', () => expect(routes).toContain('./pages/Home.tsx'));
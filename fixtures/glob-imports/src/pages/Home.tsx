@@ -0,0 +1,5 @@
+import { routes } from '../router';
+
+export default function Home() {
+  return <nav>{routes.length}</nav>;
+}
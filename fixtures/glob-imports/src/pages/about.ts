@@ -0,0 +1,3 @@
+import { routes } from '../router';
+
+export const links = () => routes;
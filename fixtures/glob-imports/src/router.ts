@@ -0,0 +1,3 @@
+const pages = import.meta.glob(['./pages/*.{ts,tsx}', '!./pages/*.test.tsx']);
+
+export const routes = Object.keys(pages);
@@ -0,0 +1,4 @@
+// Root-relative patterns depend on Vite's project root, so they aren't expanded
+const widgets = import.meta.glob(['./pages/{Home.tsx,{about,contact}.ts}', '/src/plugins/*.ts']);
+
+export const names = Object.keys(widgets);
@@ -0,0 +1,3 @@
+import { formatPrice } from '@/features/pricing';
+
+export const cartTotal = (prices: number[]) => formatPrice(prices.reduce((a, b) => a + b, 0));
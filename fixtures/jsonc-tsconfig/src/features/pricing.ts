@@ -0,0 +1,4 @@
+import { cartTotal } from '@/features/cart';
+
+export const formatPrice = (value: number) => `$${value.toFixed(2)}`;
+export const total = cartTotal;
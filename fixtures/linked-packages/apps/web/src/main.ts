@@ -0,0 +1,4 @@
+import { Button } from '@acme/ui';
+import debounce from 'lodash/debounce';
+
+export const start = debounce(() => Button(), 100);
@@ -0,0 +1,6 @@
+import { start } from '@acme/web';
+import get from 'lodash/get';
+
+export function Button() {
+  return get(start, 'name');
+}
@@ -0,0 +1,3 @@
+import { createStore } from './store';
+
+export const reducer = () => createStore;
@@ -0,0 +1,8 @@
+import { installMocks } from './testing';
+
+export const registry = new Map<string, unknown>();
+
+export function register(name: string, value: unknown) {
+  registry.set(name, value);
+  installMocks();
+}
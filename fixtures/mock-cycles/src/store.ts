@@ -0,0 +1,3 @@
+import { reducer } from './reducer';
+
+export const createStore = () => ({ reducer });
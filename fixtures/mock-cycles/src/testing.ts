@@ -0,0 +1,5 @@
+vi.mock('./registry');
+
+export function installMocks() {
+  vi.resetModules();
+}
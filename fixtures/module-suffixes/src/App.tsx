@@ -0,0 +1,7 @@
+import { loadSession } from './storage';
+
+export const APP_NAME = 'Shop';
+
+export function App() {
+  return loadSession();
+}
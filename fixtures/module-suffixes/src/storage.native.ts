@@ -0,0 +1,5 @@
+import { APP_NAME } from './App';
+
+export function loadSession() {
+  return `${APP_NAME}:async-storage`;
+}
@@ -0,0 +1,3 @@
+export function loadSession() {
+  return window.localStorage.getItem('session');
+}
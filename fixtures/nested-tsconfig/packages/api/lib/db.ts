@@ -0,0 +1,3 @@
+import { log } from '@/logger';
+
+export const query = (sql: string) => log(sql);
@@ -0,0 +1,3 @@
+import { query } from '@/db';
+
+export const log = (message: string) => console.log(message, query.name);
@@ -0,0 +1,3 @@
+import { theme } from '@/theme';
+
+export const Button = () => <button style={{ color: theme.primary }} />;
@@ -0,0 +1,3 @@
+import { Button } from '@/components/Button';
+
+export const theme = { primary: 'blue', defaultButton: Button };
@@ -0,0 +1,4 @@
+// Not in src/ui, so resolution falls through to the second replacement
+import { Icon } from '@/components/Icon';
+
+export const Card = () => <Icon />;
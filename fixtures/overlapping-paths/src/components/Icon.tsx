@@ -0,0 +1,3 @@
+import { Card } from '@/components/Card';
+
+export const Icon = () => <span data-parent={Card.name} />;
@@ -0,0 +1,7 @@
+import { migrate } from '@demo/legacy';
+
+export const VERSION = '2.0.0';
+
+export function start() {
+  return migrate();
+}
@@ -0,0 +1,5 @@
+import { VERSION } from '@demo/app';
+
+export function migrate() {
+  return `migrating to ${VERSION}`;
+}
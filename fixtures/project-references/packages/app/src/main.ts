@@ -0,0 +1,4 @@
+// `@core/*` is only mapped in the referenced core project
+import { createStore } from '@core/store';
+
+export const store = createStore();
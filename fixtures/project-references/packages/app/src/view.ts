@@ -0,0 +1,4 @@
+// Points at core's emitted declarations, which map back to core/src/format.ts
+import { format } from '../../core/dist/format';
+
+export const render = (value: number) => format(value);
@@ -0,0 +1,4 @@
+import { render } from '../../app/src/view';
+
+export const format = (value: number) => `${value}`;
+export const preview = render;
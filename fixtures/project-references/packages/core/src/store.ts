@@ -0,0 +1,3 @@
+import { store } from '../../app/src/main';
+
+export const createStore = () => ({ parent: store });
@@ -0,0 +1,12 @@
+// Generated from openapi.yaml - do not edit
+import { ApiClient } from './client';
+
+export interface Schema {
+  id: string;
+}
+
+export function validate(body: Schema) {
+  return body.id.length > 0;
+}
+
+export const defaultClient = () => new ApiClient();
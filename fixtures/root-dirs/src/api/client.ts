@@ -0,0 +1,8 @@
+import type { Schema } from './schema';
+import { validate } from './schema';
+
+export class ApiClient {
+  request(body: Schema) {
+    return validate(body);
+  }
+}
@@ -0,0 +1,4 @@
+import { writable } from 'svelte/store';
+import type Widget from './Widget.svelte';
+
+export const widgetStore = writable<typeof Widget | null>(null);
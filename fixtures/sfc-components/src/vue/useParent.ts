@@ -0,0 +1,5 @@
+import Parent from './Parent.vue';
+
+export function useParent() {
+  return { parent: Parent };
+}
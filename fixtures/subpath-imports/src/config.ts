@@ -0,0 +1,7 @@
+import { connect } from '#internal/db';
+
+export const config = {
+  url: 'postgres://localhost/app',
+};
+
+export const db = connect();
@@ -0,0 +1,3 @@
+import { db } from '#config';
+
+console.log(db.url);
@@ -0,0 +1,6 @@
+import { Shape } from './shapes';
+import { formatB } from './b.mjs';
+
+export function area(shape: Shape): string {
+  return formatB(shape.width * shape.height);
+}
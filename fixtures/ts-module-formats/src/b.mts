@@ -0,0 +1,5 @@
+import { round } from './c.cjs';
+
+export function formatB(value: number): string {
+  return `${round(value)}`;
+}
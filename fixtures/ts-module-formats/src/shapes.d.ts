@@ -0,0 +1,7 @@
+import { area } from './a.mjs';
+
+export interface Shape {
+  width: number;
+  height: number;
+  area?: typeof area;
+}
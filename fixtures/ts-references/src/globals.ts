@@ -0,0 +1,3 @@
+/// <reference path="settings.ts" />
+
+export const defaults = { theme: 'light' };
@@ -0,0 +1,5 @@
+import { store } from './store';
+
+const api = { store };
+
+export = api;
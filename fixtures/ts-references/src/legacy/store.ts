@@ -0,0 +1,3 @@
+import api = require('./api');
+
+export const store = { api };
@@ -0,0 +1,3 @@
+import { defaults } from './globals';
+
+export const settings = { ...defaults };
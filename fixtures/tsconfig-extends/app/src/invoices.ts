@@ -0,0 +1,9 @@
+import { createOrder } from '@/orders';
+
+export function formatInvoice(id: string) {
+  return `Invoice ${id}`;
+}
+
+export function reorder(id: string) {
+  return createOrder(id);
+}
@@ -0,0 +1,5 @@
+import { formatInvoice } from '@/invoices';
+
+export function createOrder(id: string) {
+  return { id, invoice: formatInvoice(id) };
+}
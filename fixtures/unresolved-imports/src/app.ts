@@ -0,0 +1,6 @@
+import { readFileSync } from 'node:fs';
+import React from 'react';
+import { UserService } from '@/users/UserService';
+import { formatDate } from '@/helpers/dates';
+
+export const app = { readFileSync, React, UserService, formatDate };
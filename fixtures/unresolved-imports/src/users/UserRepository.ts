@@ -0,0 +1,5 @@
+import type { UserService } from './UserServce';
+
+export class UserRepository {
+  owner?: UserService;
+}
@@ -0,0 +1,5 @@
+import { UserRepository } from './UserRepository';
+
+export class UserService {
+  repository = new UserRepository();
+}
@@ -0,0 +1,7 @@
+import type { Job } from './jobs';
+
+const worker = new Worker(new URL('./worker.ts', import.meta.url), { type: 'module' });
+
+export function submit(job: Job) {
+  worker.postMessage(job);
+}
@@ -0,0 +1,3 @@
+import { submit } from './main';
+
+self.onmessage = (event) => submit(event.data);
@@ -0,0 +1,936 @@
+//! Import aliases defined outside tsconfig: bundler configs and `.cddrc.json`.
+//!
+//! Vite and webpack read `resolve.alias`, and Babel's `module-resolver` plugin
+//! its `alias` option. These configs are code, so they're parsed with SWC and
+//! only simple, statically known values are understood: string literals,
+//! `path.resolve(__dirname, ...)`/`path.join(...)` (on Node's `path` module),
+//! `process.cwd()` and `fileURLToPath(new URL(..., import.meta.url))`. Anything
+//! else is skipped.
+
+use crate::filesystem::NearestAncestorCache;
+use crate::parser::parse_file;
+use crate::tsconfig::strip_jsonc;
+use crate::utils::clean_path;
+use log::debug;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use swc_ecma_ast::*;
+use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
+
+/// Bundler config files that may define aliases, in the order they're merged.
+const CONFIG_FILES: &[&str] = &[
+    "vite.config.ts",
+    "vite.config.mts",
+    "vite.config.js",
+    "vite.config.mjs",
+    "vite.config.cjs",
+    "webpack.config.js",
+    "webpack.config.ts",
+    "webpack.config.cjs",
+    "webpack.config.mjs",
+    "babel.config.js",
+    "babel.config.cjs",
+    "babel.config.mjs",
+    "babel.config.json",
+    ".babelrc",
+    ".babelrc.json",
+    ".babelrc.js",
+];
+
+const MODULE_RESOLVER: &[&str] = &["module-resolver", "babel-plugin-module-resolver"];
+
+/// Node's `path` module, whose functions are evaluated.
+const PATH_MODULES: &[&str] = &["path", "node:path"];
+
+/// Where an alias points.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AliasTarget {
+    /// A file or directory
+    Path(PathBuf),
+    /// Another module specifier (e.g. `react` -> `preact/compat`)
+    Package(String),
+}
+
+#[derive(Debug, Clone)]
+struct AliasEntry {
+    find: String,
+    /// Only the exact specifier matches (webpack's `name$` keys)
+    exact: bool,
+    /// Fallbacks, tried in order
+    targets: Vec<AliasTarget>,
+}
+
+/// A set of aliases. A key matches the specifier itself or a path below it
+/// (`@` matches `@/utils` but not `@scope/pkg`). Bundlers try their aliases in
+/// order and the first match wins; in `.cddrc.json`, whose keys have no order,
+/// the most specific (longest) match wins.
+#[derive(Debug, Clone, Default)]
+pub struct AliasMap {
+    entries: Vec<AliasEntry>,
+    longest_match: bool,
+}
+
+impl AliasMap {
+    /// Builds the aliases of a `.cddrc.json`; relative paths are resolved
+    /// against the directory of the config file.
+    pub fn from_config(aliases: &BTreeMap<String, String>, dir: &Path) -> Self {
+        let mut map = Self {
+            longest_match: true,
+            ..Self::default()
+        };
+        for (find, replacement) in aliases {
+            map.insert(find, vec![target_for(Value::Str(replacement.clone()), dir)]);
+        }
+        map
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the targets to try for a specifier, with the rest of the
+    /// specifier applied, or an empty list if no alias matches.
+    pub(crate) fn resolve(&self, import: &str) -> Vec<AliasTarget> {
+        let mut matches = self
+            .entries
+            .iter()
+            .filter_map(|entry| Some((entry, alias_rest(entry, import)?)));
+        let found = if self.longest_match {
+            matches.max_by_key(|(entry, _)| entry.find.len())
+        } else {
+            matches.next()
+        };
+        let Some((entry, rest)) = found else {
+            return Vec::new();
+        };
+
+        debug!("Matched alias '{}' for '{}'", entry.find, import);
+        entry
+            .targets
+            .iter()
+            .map(|target| match target {
+                AliasTarget::Path(path) if rest.is_empty() => AliasTarget::Path(path.clone()),
+                AliasTarget::Path(path) => AliasTarget::Path(path.join(rest)),
+                AliasTarget::Package(package) if rest.is_empty() => {
+                    AliasTarget::Package(package.clone())
+                }
+                AliasTarget::Package(package) => {
+                    AliasTarget::Package(format!("{}/{}", package.trim_end_matches('/'), rest))
+                }
+            })
+            .collect()
+    }
+
+    /// Adds an alias unless one with the same key exists (earlier configs win).
+    fn insert(&mut self, find: &str, targets: Vec<AliasTarget>) {
+        let (find, exact) = match find.strip_suffix('$') {
+            Some(find) => (find, true),
+            None => (find, false),
+        };
+        if find.is_empty() || targets.is_empty() {
+            return;
+        }
+        if self
+            .entries
+            .iter()
+            .any(|e| e.find == find && e.exact == exact)
+        {
+            return;
+        }
+        self.entries.push(AliasEntry {
+            find: find.to_string(),
+            exact,
+            targets,
+        });
+    }
+
+    fn extend(&mut self, other: AliasMap) {
+        for entry in other.entries {
+            if !self
+                .entries
+                .iter()
+                .any(|e| e.find == entry.find && e.exact == entry.exact)
+            {
+                self.entries.push(entry);
+            }
+        }
+    }
+}
+
+/// Returns what's left of `import` after the alias key, if the alias matches.
+fn alias_rest<'i>(entry: &AliasEntry, import: &'i str) -> Option<&'i str> {
+    let rest = import.strip_prefix(entry.find.as_str())?;
+    if rest.is_empty() {
+        return Some(rest);
+    }
+    if entry.exact {
+        return None;
+    }
+    // `@/` style keys already include the separator
+    if entry.find.ends_with('/') {
+        return Some(rest);
+    }
+    rest.strip_prefix('/')
+}
+
+/// Loads bundler aliases for each file from the nearest directory whose
+/// bundler configs define any. Results are cached per directory.
+#[derive(Debug, Default)]
+pub struct BundlerAliasCache {
+    /// Directory -> nearest aliases at or above it
+    nearest: NearestAncestorCache<Arc<AliasMap>>,
+}
+
+impl BundlerAliasCache {
+    /// Returns the bundler aliases that apply to a source file, if any.
+    pub fn for_file(&self, file: &Path) -> Option<Arc<AliasMap>> {
+        self.nearest
+            .get(file, |dir| load_bundler_aliases(dir).map(Arc::new))
+    }
+}
+
+/// Reads the aliases of every bundler config in `dir`. Returns None if none
+/// of them defines an alias.
+pub fn load_bundler_aliases(dir: &Path) -> Option<AliasMap> {
+    let mut aliases = AliasMap::default();
+    for name in CONFIG_FILES {
+        let path = dir.join(name);
+        if !path.is_file() {
+            continue;
+        }
+        let found = if name.ends_with(".json") || *name == ".babelrc" {
+            aliases_from_json(&path)
+        } else {
+            aliases_from_script(&path)
+        };
+        if let Some(found) = found.filter(|found| !found.is_empty()) {
+            debug!("Found aliases in {:?}", path);
+            aliases.extend(found);
+        }
+    }
+    (!aliases.is_empty()).then_some(aliases)
+}
+
+/// Babel JSON configs: every `["module-resolver", { "alias": ... }]` plugin entry,
+/// including those under `env` and `overrides`.
+fn aliases_from_json(path: &Path) -> Option<AliasMap> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&strip_jsonc(&content))
+        .map_err(|e| debug!("Failed to parse {:?}: {}", path, e))
+        .ok()?;
+    let dir = path.parent()?;
+
+    fn visit(value: &serde_json::Value, dir: &Path, aliases: &mut AliasMap) {
+        match value {
+            serde_json::Value::Array(items) => {
+                if let [serde_json::Value::String(name), serde_json::Value::Object(options), ..] =
+                    items.as_slice()
+                {
+                    if MODULE_RESOLVER.contains(&name.as_str()) {
+                        if let Some(serde_json::Value::Object(alias)) = options.get("alias") {
+                            for (find, replacement) in alias {
+                                if let Some(replacement) = replacement.as_str() {
+                                    let target = target_for(Value::Str(replacement.into()), dir);
+                                    aliases.insert(find, vec![target]);
+                                }
+                            }
+                        }
+                    }
+                }
+                items.iter().for_each(|item| visit(item, dir, aliases));
+            }
+            serde_json::Value::Object(map) => {
+                map.values().for_each(|item| visit(item, dir, aliases));
+            }
+            _ => {}
+        }
+    }
+
+    let mut aliases = AliasMap::default();
+    visit(&json, dir, &mut aliases);
+    Some(aliases)
+}
+
+/// JavaScript/TypeScript configs: `resolve.alias` objects (Vite, webpack) and
+/// `module-resolver` plugin entries (Babel), wherever they appear.
+fn aliases_from_script(path: &Path) -> Option<AliasMap> {
+    let module = parse_file(path)?;
+    let dir = path.parent()?;
+
+    // Top-level `const aliases = {...}`, so `alias: aliases` can be followed
+    let mut bindings = HashMap::new();
+    let mut path_imports = PathImports::default();
+    for item in &module.body {
+        let var = match item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) => var,
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                decl: Decl::Var(var),
+                ..
+            })) => var,
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+                path_imports.add_import(import);
+                continue;
+            }
+            _ => continue,
+        };
+        for decl in &var.decls {
+            if let Some(init) = &decl.init {
+                path_imports.add_require(&decl.name, init);
+            }
+            if let (Pat::Ident(ident), Some(init)) = (&decl.name, &decl.init) {
+                bindings.insert(ident.id.sym.to_string(), &**init);
+            }
+        }
+    }
+
+    let mut collector = AliasCollector {
+        evaluator: Evaluator {
+            config_file: path,
+            dir,
+            bindings: &bindings,
+            path_imports: &path_imports,
+        },
+        aliases: AliasMap::default(),
+    };
+    module.visit_with(&mut collector);
+    Some(collector.aliases)
+}
+
+struct AliasCollector<'a> {
+    evaluator: Evaluator<'a>,
+    aliases: AliasMap,
+}
+
+impl AliasCollector<'_> {
+    /// Reads an alias map: `{ find: replacement }` or `[{ find, replacement }]`.
+    fn collect(&mut self, alias: &Expr) {
+        let eval = &self.evaluator;
+        match eval.unwrap(alias) {
+            Expr::Object(object) => {
+                for (find, value) in eval.entries(object) {
+                    let targets = match eval.unwrap(value) {
+                        // webpack fallbacks
+                        Expr::Array(array) => array
+                            .elems
+                            .iter()
+                            .flatten()
+                            .filter_map(|elem| eval.target(&elem.expr))
+                            .collect(),
+                        value => eval.target(value).into_iter().collect(),
+                    };
+                    self.aliases.insert(&find, targets);
+                }
+            }
+            Expr::Array(array) => {
+                for elem in array.elems.iter().flatten() {
+                    let Expr::Object(object) = eval.unwrap(&elem.expr) else {
+                        continue;
+                    };
+                    let entries = eval.entries(object);
+                    let get = |key: &str| entries.iter().find(|(k, _)| k == key).map(|(_, v)| *v);
+                    // Regex `find` patterns can't be matched statically
+                    let Some(Value::Str(find)) = get("find").and_then(|v| eval.value(v)) else {
+                        debug!("Skipping alias without a string `find`");
+                        continue;
+                    };
+                    if let Some(target) = get("replacement").and_then(|v| eval.target(v)) {
+                        self.aliases.insert(&find, vec![target]);
+                    }
+                }
+            }
+            _ => debug!("Skipping alias map that isn't an object or array literal"),
+        }
+    }
+}
+
+impl Visit for AliasCollector<'_> {
+    noop_visit_type!();
+
+    fn visit_object_lit(&mut self, object: &ObjectLit) {
+        let eval = &self.evaluator;
+        let alias = eval
+            .entries(object)
+            .into_iter()
+            .find(|(key, _)| key == "resolve")
+            .and_then(|(_, resolve)| match eval.unwrap(resolve) {
+                Expr::Object(resolve) => eval
+                    .entries(resolve)
+                    .into_iter()
+                    .find(|(key, _)| key == "alias")
+                    .map(|(_, alias)| alias),
+                _ => None,
+            });
+        if let Some(alias) = alias {
+            self.collect(alias);
+        }
+        object.visit_children_with(self);
+    }
+
+    fn visit_array_lit(&mut self, array: &ArrayLit) {
+        let eval = &self.evaluator;
+        let mut elems = array.elems.iter().flatten().map(|elem| &*elem.expr);
+        let plugin = elems.next().and_then(|name| eval.value(name));
+        if let (Some(Value::Str(name)), Some(Expr::Object(options))) =
+            (plugin, elems.next().map(|options| eval.unwrap(options)))
+        {
+            if MODULE_RESOLVER.contains(&name.as_str()) {
+                let alias = eval
+                    .entries(options)
+                    .into_iter()
+                    .find(|(key, _)| key == "alias")
+                    .map(|(_, alias)| alias);
+                if let Some(alias) = alias {
+                    self.collect(alias);
+                }
+            }
+        }
+        array.visit_children_with(self);
+    }
+}
+
+/// The names a config file binds to Node's `path` module and its functions.
+#[derive(Debug, Default)]
+struct PathImports {
+    /// `import path from 'node:path'`, `const p = require('path')`
+    modules: HashSet<String>,
+    /// `import { resolve, join as joinPath } from 'path'`: local name -> function
+    functions: HashMap<String, String>,
+}
+
+impl PathImports {
+    fn add_import(&mut self, import: &ImportDecl) {
+        if !PATH_MODULES.contains(&import.src.value.as_ref()) {
+            return;
+        }
+        for specifier in &import.specifiers {
+            match specifier {
+                ImportSpecifier::Default(default) => {
+                    self.modules.insert(default.local.sym.to_string());
+                }
+                ImportSpecifier::Namespace(namespace) => {
+                    self.modules.insert(namespace.local.sym.to_string());
+                }
+                ImportSpecifier::Named(named) => {
+                    let imported = match &named.imported {
+                        Some(ModuleExportName::Ident(ident)) => ident.sym.to_string(),
+                        Some(ModuleExportName::Str(s)) => s.value.to_string(),
+                        None => named.local.sym.to_string(),
+                    };
+                    self.functions.insert(named.local.sym.to_string(), imported);
+                }
+            }
+        }
+    }
+
+    /// `const path = require('path')` or `const { resolve } = require('path')`
+    fn add_require(&mut self, name: &Pat, init: &Expr) {
+        let Expr::Call(call) = init else {
+            return;
+        };
+        let is_require = matches!(
+            &call.callee,
+            Callee::Expr(callee) if matches!(&**callee, Expr::Ident(i) if i.sym.as_ref() == "require")
+        );
+        let is_path = matches!(
+            call.args.first().map(|arg| &*arg.expr),
+            Some(Expr::Lit(Lit::Str(s))) if PATH_MODULES.contains(&s.value.as_ref())
+        );
+        if !is_require || !is_path {
+            return;
+        }
+        match name {
+            Pat::Ident(ident) => {
+                self.modules.insert(ident.id.sym.to_string());
+            }
+            Pat::Object(object) => {
+                for prop in &object.props {
+                    match prop {
+                        ObjectPatProp::Assign(assign) => {
+                            let name = assign.key.sym.to_string();
+                            self.functions.insert(name.clone(), name);
+                        }
+                        ObjectPatProp::KeyValue(kv) => {
+                            if let (PropName::Ident(key), Pat::Ident(local)) = (&kv.key, &*kv.value)
+                            {
+                                self.functions
+                                    .insert(local.id.sym.to_string(), key.sym.to_string());
+                            }
+                        }
+                        ObjectPatProp::Rest(_) => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether `expr` is the `path` module
+    fn is_module(&self, expr: &Expr) -> bool {
+        matches!(expr, Expr::Ident(ident)
+            if ident.sym.as_ref() == "path" || self.modules.contains(ident.sym.as_ref()))
+    }
+}
+
+/// A statically evaluated value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Value {
+    /// An absolute path, produced by `path.resolve()`, `__dirname` and the like
+    Path(PathBuf),
+    /// A plain string
+    Str(String),
+}
+
+/// Evaluates the handful of expressions alias values are usually built from.
+/// The working directory of the bundler is assumed to be the config's directory.
+struct Evaluator<'a> {
+    config_file: &'a Path,
+    dir: &'a Path,
+    bindings: &'a HashMap<String, &'a Expr>,
+    path_imports: &'a PathImports,
+}
+
+impl<'a> Evaluator<'a> {
+    /// Strips parentheses and TypeScript assertions, and follows top-level constants.
+    fn unwrap<'e>(&self, mut expr: &'e Expr) -> &'e Expr
+    where
+        'a: 'e,
+    {
+        // Bounded, so `const a = b, b = a` can't loop
+        for _ in 0..8 {
+            expr = match expr {
+                Expr::Paren(paren) => &paren.expr,
+                Expr::TsAs(ts) => &ts.expr,
+                Expr::TsSatisfies(ts) => &ts.expr,
+                Expr::TsConstAssertion(ts) => &ts.expr,
+                Expr::Ident(ident) => match self.bindings.get(ident.sym.as_ref()) {
+                    Some(bound) => bound,
+                    None => return expr,
+                },
+                _ => return expr,
+            };
+        }
+        expr
+    }
+
+    /// Key/value pairs of an object literal; shorthand properties are followed.
+    fn entries<'e>(&self, object: &'e ObjectLit) -> Vec<(String, &'e Expr)>
+    where
+        'a: 'e,
+    {
+        object
+            .props
+            .iter()
+            .filter_map(|prop| match prop {
+                PropOrSpread::Prop(prop) => match &**prop {
+                    Prop::KeyValue(kv) => {
+                        let key = match &kv.key {
+                            PropName::Ident(ident) => ident.sym.to_string(),
+                            PropName::Str(s) => s.value.to_string(),
+                            _ => return None,
+                        };
+                        Some((key, &*kv.value))
+                    }
+                    Prop::Shorthand(ident) => {
+                        let bound = self.bindings.get(ident.sym.as_ref())?;
+                        Some((ident.sym.to_string(), *bound))
+                    }
+                    _ => None,
+                },
+                PropOrSpread::Spread(_) => None,
+            })
+            .collect()
+    }
+
+    fn target(&self, expr: &Expr) -> Option<AliasTarget> {
+        let value = self.value(expr);
+        if value.is_none() {
+            debug!("Skipping alias value that can't be evaluated statically");
+        }
+        Some(target_for(value?, self.dir))
+    }
+
+    fn value(&self, expr: &Expr) -> Option<Value> {
+        match self.unwrap(expr) {
+            Expr::Lit(Lit::Str(s)) => Some(Value::Str(s.value.to_string())),
+            Expr::Tpl(tpl) if tpl.exprs.is_empty() => {
+                let cooked = tpl.quasis.first()?.cooked.as_ref()?;
+                Some(Value::Str(cooked.to_string()))
+            }
+            Expr::Ident(ident) if ident.sym.as_ref() == "__dirname" => {
+                Some(Value::Path(self.dir.to_path_buf()))
+            }
+            Expr::Ident(ident) if ident.sym.as_ref() == "__filename" => {
+                Some(Value::Path(self.config_file.to_path_buf()))
+            }
+            Expr::Member(member) => match (&*member.obj, &member.prop) {
+                // import.meta.dirname / import.meta.url
+                (Expr::MetaProp(_), MemberProp::Ident(prop)) => match prop.sym.as_ref() {
+                    "dirname" => Some(Value::Path(self.dir.to_path_buf())),
+                    "url" | "filename" => Some(Value::Path(self.config_file.to_path_buf())),
+                    _ => None,
+                },
+                // new URL('./src', import.meta.url).pathname
+                (obj, MemberProp::Ident(prop)) if prop.sym.as_ref() == "pathname" => {
+                    self.value(obj)
+                }
+                _ => None,
+            },
+            Expr::New(new) => {
+                // new URL('./src', import.meta.url)
+                let Expr::Ident(callee) = &*new.callee else {
+                    return None;
+                };
+                let args = new.args.as_ref()?;
+                if callee.sym.as_ref() != "URL" || args.len() != 2 {
+                    return None;
+                }
+                let Value::Str(relative) = self.value(&args[0].expr)? else {
+                    return None;
+                };
+                let Value::Path(base) = self.value(&args[1].expr)? else {
+                    return None;
+                };
+                Some(Value::Path(clean_path(&base.parent()?.join(relative))))
+            }
+            Expr::Call(call) => self.call(call),
+            _ => None,
+        }
+    }
+
+    /// `path.resolve()`, `path.join()`, `path.dirname()`, `fileURLToPath()`,
+    /// `process.cwd()` and `require.resolve()`, called directly or as imported functions.
+    /// `resolve`, `join` and `dirname` only count on the `path` module, so
+    /// `['a', 'b'].join('/')` isn't mistaken for a path.
+    fn call(&self, call: &CallExpr) -> Option<Value> {
+        let Callee::Expr(callee) = &call.callee else {
+            return None;
+        };
+        let (name, on_path) = match &**callee {
+            Expr::Ident(ident) => match self.path_imports.functions.get(ident.sym.as_ref()) {
+                Some(function) => (function.clone(), true),
+                None => (ident.sym.to_string(), false),
+            },
+            Expr::Member(member) => match &member.prop {
+                MemberProp::Ident(prop) => (
+                    prop.sym.to_string(),
+                    self.path_imports.is_module(&member.obj),
+                ),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let args = call
+            .args
+            .iter()
+            .map(|arg| self.value(&arg.expr))
+            .collect::<Option<Vec<_>>>()?;
+
+        // `require.resolve('preact/compat')` names a module, not a path
+        if let Expr::Member(member) = &**callee {
+            if matches!(&*member.obj, Expr::Ident(obj) if obj.sym.as_ref() == "require") {
+                return match (name.as_str(), args.as_slice()) {
+                    ("resolve", [Value::Str(specifier)]) => Some(Value::Str(specifier.clone())),
+                    _ => None,
+                };
+            }
+        }
+
+        match (name.as_str(), args.as_slice()) {
+            ("cwd", []) => Some(Value::Path(self.dir.to_path_buf())),
+            ("fileURLToPath", [value]) => Some(value.clone()),
+            ("dirname", [Value::Path(path)]) if on_path => {
+                Some(Value::Path(path.parent()?.to_path_buf()))
+            }
+            ("resolve" | "join", _) if on_path => {
+                // `resolve` starts from the working directory, `join` doesn't
+                let mut joined = match name.as_str() {
+                    "resolve" => self.dir.to_path_buf(),
+                    _ => PathBuf::new(),
+                };
+                for arg in args {
+                    match arg {
+                        Value::Path(path) => joined = path,
+                        Value::Str(s) => joined.push(s),
+                    }
+                }
+                if joined.is_absolute() {
+                    Some(Value::Path(clean_path(&joined)))
+                } else {
+                    Some(Value::Str(joined.to_string_lossy().into_owned()))
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Relative strings are resolved against `dir`. Root-relative ones (`/src`) are
+/// resolved against `dir` too, as Vite does for its project root, unless only the
+/// absolute path exists. Anything else names a module.
+fn target_for(value: Value, dir: &Path) -> AliasTarget {
+    match value {
+        Value::Path(path) => AliasTarget::Path(path),
+        Value::Str(s) if s == "." || s.starts_with("./") || s.starts_with("../") => {
+            AliasTarget::Path(clean_path(&dir.join(s)))
+        }
+        Value::Str(s) if s.starts_with('/') => {
+            let root_relative = clean_path(&dir.join(s.trim_start_matches('/')));
+            let absolute = PathBuf::from(&s);
+            if root_relative.exists() || !absolute.exists() {
+                AliasTarget::Path(root_relative)
+            } else {
+                AliasTarget::Path(absolute)
+            }
+        }
+        Value::Str(s) => AliasTarget::Package(s),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_alias_matching() {
+        let dir = Path::new("/project");
+        let aliases = AliasMap::from_config(
+            &BTreeMap::from([
+                ("@".to_string(), "./src".to_string()),
+                ("@components".to_string(), "./src/components".to_string()),
+                ("react".to_string(), "preact/compat".to_string()),
+            ]),
+            dir,
+        );
+
+        assert_eq!(
+            aliases.resolve("@/utils/date"),
+            vec![AliasTarget::Path(PathBuf::from("/project/src/utils/date"))]
+        );
+        assert_eq!(
+            aliases.resolve("@components/button"),
+            vec![AliasTarget::Path(PathBuf::from(
+                "/project/src/components/button"
+            ))]
+        );
+        assert_eq!(
+            aliases.resolve("react/jsx-runtime"),
+            vec![AliasTarget::Package(
+                "preact/compat/jsx-runtime".to_string()
+            )]
+        );
+        assert!(aliases.resolve("@scope/pkg").is_empty());
+        assert!(aliases.resolve("reactive").is_empty());
+    }
+
+    #[test]
+    fn test_vite_config_aliases() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        fs::write(
+            dir.join("vite.config.ts"),
+            r#"
+import { defineConfig } from 'vite';
+import path from 'node:path';
+import { fileURLToPath, URL } from 'node:url';
+
+export default defineConfig({
+  resolve: {
+    alias: {
+      '@': fileURLToPath(new URL('./src', import.meta.url)),
+      '~lib': path.resolve(__dirname, 'lib'),
+      utils: `/shared/utils`,
+      dynamic: process.env.DYNAMIC,
+    },
+  },
+});
+"#,
+        )
+        .unwrap();
+
+        let aliases = load_bundler_aliases(dir).unwrap();
+        assert_eq!(
+            aliases.resolve("@/app"),
+            vec![AliasTarget::Path(dir.join("src/app"))]
+        );
+        assert_eq!(
+            aliases.resolve("~lib/math"),
+            vec![AliasTarget::Path(dir.join("lib/math"))]
+        );
+        assert_eq!(
+            aliases.resolve("utils"),
+            vec![AliasTarget::Path(dir.join("shared/utils"))]
+        );
+        assert!(aliases.resolve("dynamic").is_empty());
+    }
+
+    #[test]
+    fn test_webpack_and_babel_aliases() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        fs::write(
+            dir.join("webpack.config.js"),
+            r#"
+const path = require('path');
+const aliases = {
+  'config$': path.join(__dirname, 'src/config.js'),
+  '@shared': [path.resolve(__dirname, 'shared'), path.resolve(__dirname, 'vendor')],
+  react: require.resolve('preact/compat'),
+};
+module.exports = { resolve: { alias: aliases } };
+"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join(".babelrc"),
+            r#"{
+  // Babel allows comments here
+  "plugins": [["module-resolver", { "alias": { "@": "./src" } }]]
+}"#,
+        )
+        .unwrap();
+
+        let aliases = load_bundler_aliases(dir).unwrap();
+        assert_eq!(
+            aliases.resolve("config"),
+            vec![AliasTarget::Path(dir.join("src/config.js"))]
+        );
+        assert!(aliases.resolve("config/other").is_empty());
+        assert_eq!(
+            aliases.resolve("react"),
+            vec![AliasTarget::Package("preact/compat".to_string())]
+        );
+        assert_eq!(
+            aliases.resolve("@shared/x"),
+            vec![
+                AliasTarget::Path(dir.join("shared/x")),
+                AliasTarget::Path(dir.join("vendor/x"))
+            ]
+        );
+        assert_eq!(
+            aliases.resolve("@/x"),
+            vec![AliasTarget::Path(dir.join("src/x"))]
+        );
+    }
+
+    #[test]
+    fn test_vite_alias_array_and_babel_plugin() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        fs::create_dir(dir.join("app")).unwrap();
+        fs::write(
+            dir.join("vite.config.js"),
+            r#"
+export default {
+  resolve: {
+    alias: [
+      { find: '#app', replacement: '/app' },
+      { find: /^~(.*)$/, replacement: '$1' },
+    ],
+  },
+};
+"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("babel.config.js"),
+            r#"
+module.exports = (api) => ({
+  plugins: [['babel-plugin-module-resolver', { root: ['./src'], alias: { test: './test' } }]],
+});
+"#,
+        )
+        .unwrap();
+
+        let aliases = load_bundler_aliases(dir).unwrap();
+        assert_eq!(
+            aliases.resolve("#app/store"),
+            vec![AliasTarget::Path(dir.join("app/store"))]
+        );
+        assert_eq!(
+            aliases.resolve("test/setup"),
+            vec![AliasTarget::Path(dir.join("test/setup"))]
+        );
+        assert!(aliases.resolve("~foo").is_empty());
+    }
+
+    #[test]
+    fn test_bundler_aliases_match_in_order() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        fs::write(
+            dir.join("vite.config.ts"),
+            r#"
+import { resolve as r } from 'node:path';
+
+export default {
+  resolve: {
+    alias: [
+      { find: '@', replacement: r(__dirname, 'src') },
+      { find: '@/components', replacement: r(__dirname, 'legacy') },
+      { find: 'joined', replacement: ['src', 'lib'].join('/') },
+    ],
+  },
+};
+"#,
+        )
+        .unwrap();
+
+        // Vite tries its aliases in order, so the more specific one never matches
+        let aliases = load_bundler_aliases(dir).unwrap();
+        assert_eq!(
+            aliases.resolve("@/components/button"),
+            vec![AliasTarget::Path(dir.join("src/components/button"))]
+        );
+        // `join` on an array isn't `path.join`
+        assert!(aliases.resolve("joined").is_empty());
+    }
+
+    #[test]
+    fn test_path_module_bindings() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        fs::write(
+            dir.join("webpack.config.js"),
+            r#"
+const nodePath = require('node:path');
+const { join } = require('path');
+const url = { resolve: () => '/elsewhere' };
+module.exports = {
+  resolve: {
+    alias: {
+      lib: nodePath.resolve(__dirname, 'lib'),
+      shared: join(__dirname, 'shared'),
+      remote: url.resolve('src'),
+    },
+  },
+};
+"#,
+        )
+        .unwrap();
+
+        let aliases = load_bundler_aliases(dir).unwrap();
+        assert_eq!(
+            aliases.resolve("lib"),
+            vec![AliasTarget::Path(dir.join("lib"))]
+        );
+        assert_eq!(
+            aliases.resolve("shared"),
+            vec![AliasTarget::Path(dir.join("shared"))]
+        );
+        assert!(aliases.resolve("remote").is_empty());
+    }
+
+    #[test]
+    fn test_no_bundler_config() {
+        let temp = TempDir::new().unwrap();
+        fs::write(
+            temp.path().join("vite.config.ts"),
+            "export default { plugins: [] };",
+        )
+        .unwrap();
+        assert!(load_bundler_aliases(temp.path()).is_none());
+    }
+}
@@ -0,0 +1,517 @@
+//! The `cdd` command: parses arguments, merges them with the config file, runs
+//! the analysis and reports the result through the exit code.
+
+use crate::config::{find_config, update_config_hash, CliOptions, MergedConfig};
+use crate::graph::{
+    BoundaryEdge, CycleInfo, CycleOptions, DependencyGraph, ResolveOptions, UnresolvedImport,
+};
+use crate::output::{
+    compute_cycles_hash, filter_allowed_cycles, generate_json_output, print_boundary_edges,
+    print_cycles_detailed, print_json_error, print_json_output, print_mock_cycles,
+    print_unresolved_imports, JsonOutput, OutputFormat,
+};
+use crate::parser::ParserOptions;
+use crate::tsconfig::{load_tsconfig, TsConfigCache};
+#[cfg(feature = "watch")]
+use crate::watch;
+use crate::workspace::Workspace;
+use crate::{cli, config, filesystem, graph, resolver};
+use ::colored::*;
+use env_logger::Builder;
+use log::info;
+use std::path::Path;
+use std::time::Instant;
+
+/// Result of cycle analysis
+struct AnalysisResult {
+    /// Cycles after filtering allowed ones
+    filtered_cycles: Vec<CycleInfo>,
+    /// Total number of files analyzed
+    total_files: usize,
+    /// Hash of all cycles (computed before filtering)
+    cycles_hash: String,
+    /// Cycles that only exist through test-framework mocks (reported separately)
+    mock_cycles: Vec<CycleInfo>,
+    /// Imports that resolve outside the scanned directory
+    boundary_edges: Vec<BoundaryEdge>,
+    /// Third-party packages recorded as external nodes
+    external_packages: Vec<String>,
+    /// Imports that should point at a project file but don't resolve
+    unresolved_imports: Vec<UnresolvedImport>,
+}
+
+impl AnalysisResult {
+    fn to_json(&self, root: &Path, workspace: Option<&Workspace>) -> JsonOutput {
+        let mut output = generate_json_output(
+            &self.filtered_cycles,
+            &self.mock_cycles,
+            &self.boundary_edges,
+            &self.external_packages,
+            &self.unresolved_imports,
+            root,
+            self.total_files,
+        );
+        output.workspace_format = workspace.map(|ws| ws.format);
+        output
+    }
+}
+
+/// Runs the `cdd` command with the process arguments, then exits.
+pub fn run() {
+    let cli = cli::parse_args();
+
+    // For JSON output, we don't initialize normal logging
+    let output_format = if cli.json {
+        OutputFormat::Json
+    } else {
+        OutputFormat::Text
+    };
+
+    // Only initialize logger for non-JSON output
+    if output_format != OutputFormat::Json {
+        initialize_logger(cli.debug, cli.silent);
+    }
+
+    // Validate that the input directory exists
+    let dir_path = Path::new(&cli.dir);
+    if !dir_path.exists() {
+        if output_format == OutputFormat::Json {
+            print_json_error(&format!("Directory '{}' does not exist", cli.dir));
+        } else {
+            eprintln!(
+                "{} Directory '{}' does not exist",
+                "Error:".red().bold(),
+                cli.dir
+            );
+        }
+        std::process::exit(1);
+    }
+    if !dir_path.is_dir() {
+        if output_format == OutputFormat::Json {
+            print_json_error(&format!("'{}' is not a directory", cli.dir));
+        } else {
+            eprintln!("{} '{}' is not a directory", "Error:".red().bold(), cli.dir);
+        }
+        std::process::exit(1);
+    }
+
+    // Load config file if present
+    let canonical_dir = dir_path
+        .canonicalize()
+        .unwrap_or_else(|_| dir_path.to_path_buf());
+
+    let file_config = find_config(&canonical_dir).map(|(_, config)| config);
+
+    // Merge CLI args with config file (CLI takes precedence)
+    let merged = MergedConfig::new(
+        CliOptions {
+            exclude: cli.exclude,
+            ignore_type_imports: cli.ignore_type_imports,
+            expected_cycles: cli.number_of_cycles,
+            tsconfig_path: cli.tsconfig_path,
+            expected_hash: cli.expected_hash,
+            allowlist_path: cli.allowlist,
+            eager_only: cli.eager_only,
+            mock_imports: cli.mock_imports,
+            include_workers: cli.include_workers,
+            module_suffixes: cli.module_suffixes,
+            platform_suffixes: cli.platform_suffixes,
+            conditions: cli.conditions,
+            external_nodes: cli.external_nodes,
+            resolvers: cli.resolvers,
+            fail_on_unresolved: cli.fail_on_unresolved,
+        },
+        file_config,
+    );
+
+    // Load the explicit tsconfig, or find the nearest one for each file
+    let tsconfigs = if cli.no_tsconfig {
+        None
+    } else if let Some(ref tsconfig_path) = merged.tsconfig_path {
+        // Explicit path provided
+        let path = Path::new(tsconfig_path);
+        let aliases = load_tsconfig(path);
+        if aliases.is_none() && output_format != OutputFormat::Json {
+            log::warn!("Could not load tsconfig from '{}'", tsconfig_path);
+        }
+        aliases.map(TsConfigCache::explicit)
+    } else {
+        Some(TsConfigCache::nearest())
+    };
+
+    let parser_options = ParserOptions {
+        ignore_type_imports: merged.ignore_type_imports,
+        // Without `verbatimModuleSyntax`, tsc erases imports only used as types too
+        ignore_elided_imports: merged.ignore_type_imports,
+    };
+
+    let cycle_options = CycleOptions {
+        eager_only: merged.eager_only,
+        mock_imports: merged.mock_imports,
+        include_workers: merged.include_workers,
+    };
+
+    // Auto-detect workspace (unless --no-workspace)
+    let workspace = if cli.no_workspace {
+        None
+    } else {
+        match Workspace::detect(&canonical_dir) {
+            Some(ws) => {
+                if output_format != OutputFormat::Json {
+                    log::info!(
+                        "Detected workspace with {} packages ({}): {}",
+                        ws.packages.len(),
+                        ws.format,
+                        ws.packages.keys().cloned().collect::<Vec<_>>().join(", ")
+                    );
+                    if ws.root != canonical_dir {
+                        log::info!("Using workspace root {}", ws.root.display());
+                    }
+                }
+                Some(ws)
+            }
+            None => None,
+        }
+    };
+
+    #[cfg(feature = "watch")]
+    if cli.watch {
+        // Watch mode: run analysis and re-run on file changes
+        let dir = cli.dir.clone();
+
+        if let Err(e) = watch::watch_and_run(&canonical_dir, &merged.exclude, || {
+            log::info!("Starting analysis in directory: {}", dir);
+            let start = Instant::now();
+            let result = run_analysis(
+                &dir,
+                &merged,
+                &parser_options,
+                &cycle_options,
+                tsconfigs.as_ref(),
+                workspace.as_ref(),
+                &canonical_dir,
+            );
+            log::info!("Analysis completed in {:.2?}", start.elapsed());
+
+            // Print detailed output
+            print_cycles_detailed(&result.filtered_cycles, &canonical_dir);
+            print_mock_cycles(&result.mock_cycles, &canonical_dir);
+            print_boundary_edges(&result.boundary_edges, &canonical_dir);
+            print_unresolved_imports(&result.unresolved_imports, &canonical_dir);
+
+            // Check expected cycles count
+            if merged.expected_cycles != result.filtered_cycles.len() {
+                info!(
+                    "Expected {} cycle(s), but found {} cycle(s).",
+                    merged.expected_cycles.to_string().bright_green().bold(),
+                    result.filtered_cycles.len().to_string().red().bold()
+                );
+            } else {
+                info!(
+                    "Expected {} cycle(s) and found {} cycle(s).",
+                    merged.expected_cycles.to_string().bright_green().bold(),
+                    result
+                        .filtered_cycles
+                        .len()
+                        .to_string()
+                        .bright_green()
+                        .bold()
+                );
+            }
+
+            // Check expected hash if specified
+            if let Some(ref expected_hash) = merged.expected_hash {
+                if expected_hash != &result.cycles_hash {
+                    info!(
+                        "Hash mismatch: expected {}, got {}",
+                        expected_hash.bright_green().bold(),
+                        result.cycles_hash.red().bold()
+                    );
+                }
+            }
+        }) {
+            eprintln!(
+                "{} Failed to start watch mode: {}",
+                "Error:".red().bold(),
+                e
+            );
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    {
+        // Single run mode
+        if output_format != OutputFormat::Json {
+            log::info!("Starting analysis in directory: {}", cli.dir);
+        }
+
+        let start = Instant::now();
+        let result = run_analysis(
+            &cli.dir,
+            &merged,
+            &parser_options,
+            &cycle_options,
+            tsconfigs.as_ref(),
+            workspace.as_ref(),
+            &canonical_dir,
+        );
+
+        if output_format != OutputFormat::Json {
+            log::info!("Analysis completed in {:.2?}", start.elapsed());
+        }
+
+        // Handle --init flag
+        if cli.init {
+            match config::init_config(&canonical_dir, &result.filtered_cycles) {
+                Ok(config_path) => {
+                    if output_format == OutputFormat::Json {
+                        let json_output = result.to_json(&canonical_dir, workspace.as_ref());
+                        print_json_output(&json_output);
+                        eprintln!("Initialized {}", config_path.display());
+                    } else {
+                        info!(
+                            "{} Initialized {} with {} allowed cycle(s)",
+                            "OK".green().bold(),
+                            config_path.display(),
+                            result.filtered_cycles.len()
+                        );
+                        info!(
+                            "All current cycles are now in the allowlist. New cycles will cause failures."
+                        );
+                    }
+                    std::process::exit(0);
+                }
+                Err(e) => {
+                    if output_format == OutputFormat::Json {
+                        print_json_error(&e);
+                    } else {
+                        eprintln!("{} {}", "Error:".red().bold(), e);
+                    }
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        // Handle --update-hash flag
+        if cli.update_hash {
+            match update_config_hash(&canonical_dir, &result.cycles_hash) {
+                Ok(config_path) => {
+                    if output_format == OutputFormat::Json {
+                        // Include update info in JSON output
+                        let json_output = result.to_json(&canonical_dir, workspace.as_ref());
+                        print_json_output(&json_output);
+                        eprintln!("Updated expected_hash in {}", config_path.display());
+                    } else {
+                        print_cycles_detailed(&result.filtered_cycles, &canonical_dir);
+                        info!(
+                            "{} Updated expected_hash to {} in {}",
+                            "OK".green().bold(),
+                            result.cycles_hash.bright_green().bold(),
+                            config_path.display()
+                        );
+                    }
+                    std::process::exit(0);
+                }
+                Err(e) => {
+                    if output_format == OutputFormat::Json {
+                        print_json_error(&e);
+                    } else {
+                        eprintln!("{} {}", "Error:".red().bold(), e);
+                    }
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        // Determine exit code based on validation
+        let mut exit_code = 0;
+        let broken_imports = result
+            .unresolved_imports
+            .iter()
+            .filter(|import| import.kind.is_broken())
+            .count();
+        if merged.fail_on_unresolved && broken_imports > 0 {
+            exit_code = 1;
+        }
+
+        if output_format == OutputFormat::Json {
+            // JSON output mode
+            let json_output = result.to_json(&canonical_dir, workspace.as_ref());
+            print_json_output(&json_output);
+
+            // Still validate and set exit code
+            if merged.expected_cycles != result.filtered_cycles.len() {
+                exit_code = 1;
+            }
+            if let Some(ref expected_hash) = merged.expected_hash {
+                if expected_hash != &result.cycles_hash {
+                    exit_code = 1;
+                }
+            }
+        } else {
+            // Text output mode - use detailed output
+            print_cycles_detailed(&result.filtered_cycles, &canonical_dir);
+            print_mock_cycles(&result.mock_cycles, &canonical_dir);
+            print_boundary_edges(&result.boundary_edges, &canonical_dir);
+            print_unresolved_imports(&result.unresolved_imports, &canonical_dir);
+
+            // Show hash for reference
+            if !result.filtered_cycles.is_empty() {
+                info!("Cycles hash: {}", result.cycles_hash.dimmed());
+            }
+
+            if merged.fail_on_unresolved && broken_imports > 0 {
+                info!(
+                    "{} {} relative or aliased import(s) don't resolve.",
+                    "X".red().bold(),
+                    broken_imports.to_string().red().bold()
+                );
+            }
+
+            // Check expected cycles count
+            if merged.expected_cycles != result.filtered_cycles.len() {
+                info!(
+                    "{} Expected {} cycle(s), but found {} cycle(s).",
+                    "X".red().bold(),
+                    merged.expected_cycles.to_string().bright_green().bold(),
+                    result.filtered_cycles.len().to_string().red().bold()
+                );
+                exit_code = 1;
+            } else {
+                info!(
+                    "{} Expected {} cycle(s) and found {} cycle(s).",
+                    "OK".green().bold(),
+                    merged.expected_cycles.to_string().bright_green().bold(),
+                    result
+                        .filtered_cycles
+                        .len()
+                        .to_string()
+                        .bright_green()
+                        .bold()
+                );
+            }
+
+            // Check expected hash if specified
+            if let Some(ref expected_hash) = merged.expected_hash {
+                if expected_hash != &result.cycles_hash {
+                    info!(
+                        "{} Hash mismatch: expected {}, got {}",
+                        "X".red().bold(),
+                        expected_hash.bright_green().bold(),
+                        result.cycles_hash.red().bold()
+                    );
+                    exit_code = 1;
+                } else {
+                    info!(
+                        "{} Hash matches: {}",
+                        "OK".green().bold(),
+                        expected_hash.bright_green().bold()
+                    );
+                }
+            }
+        }
+
+        std::process::exit(exit_code);
+    }
+}
+
+/// Initializes the logger with appropriate log level based on the debug flag.
+fn initialize_logger(debug: bool, silent: bool) {
+    let mut builder = Builder::new();
+
+    if silent {
+        builder.filter_level(log::LevelFilter::Off);
+    } else if debug {
+        builder.filter_level(log::LevelFilter::Debug);
+    } else {
+        builder.filter_level(log::LevelFilter::Info);
+    }
+
+    builder.init();
+}
+
+fn run_analysis(
+    dir: &str,
+    config: &MergedConfig,
+    parser_options: &ParserOptions,
+    cycle_options: &CycleOptions,
+    tsconfigs: Option<&TsConfigCache>,
+    workspace: Option<&Workspace>,
+    root: &Path,
+) -> AnalysisResult {
+    let allowed_cycles = &config.allowed_cycles;
+
+    // Collect all relevant files
+    let files = filesystem::collect_files(dir, &config.exclude);
+    let total_files = files.len();
+    info!("Collected {} files.", total_files);
+
+    // Build the dependency graph
+    let resolve_options = ResolveOptions {
+        module_suffixes: config.module_suffixes,
+        platform_suffixes: config.platform_suffixes.clone(),
+        conditions: config.conditions.clone(),
+        external_nodes: config.external_nodes,
+        aliases: config.aliases.clone(),
+        resolvers: resolver::resolvers_from_names(&config.resolvers),
+    };
+    let DependencyGraph {
+        graph,
+        boundary_edges,
+        external_packages,
+        unresolved_imports,
+    } = graph::build_dependency_graph(
+        &files,
+        root,
+        parser_options,
+        &resolve_options,
+        tsconfigs,
+        workspace,
+    );
+    info!(
+        "Built dependency graph with {} nodes and {} edges.",
+        graph.node_count(),
+        graph.edge_count()
+    );
+    if !external_packages.is_empty() {
+        info!(
+            "Recorded {} external package(s): {}",
+            external_packages.len(),
+            external_packages.join(", ")
+        );
+    }
+
+    // Detect unique cycles (pass root for stable hash computation)
+    let all_cycles = graph::get_unique_cycles(&graph, root, cycle_options);
+    let all_cycles_count = all_cycles.len();
+    let mock_cycles = graph::get_mock_cycles(&graph, root, cycle_options);
+
+    // Compute hash before filtering
+    let cycles_hash = compute_cycles_hash(&all_cycles);
+
+    // Filter allowed cycles
+    let filtered_cycles = if allowed_cycles.is_empty() {
+        all_cycles
+    } else {
+        let filtered = filter_allowed_cycles(all_cycles, allowed_cycles, root);
+        if filtered.len() < all_cycles_count {
+            info!(
+                "Filtered {} allowed cycle(s), {} remaining.",
+                all_cycles_count - filtered.len(),
+                filtered.len()
+            );
+        }
+        filtered
+    };
+
+    AnalysisResult {
+        filtered_cycles,
+        total_files,
+        cycles_hash,
+        mock_cycles,
+        boundary_edges,
+        external_packages,
+        unresolved_imports,
+    }
+}
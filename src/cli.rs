@@ -1,3 +1,6 @@
+use crate::graph::{MockImports, ModuleSuffixes, PlatformSuffixes};
+use crate::resolver::DEFAULT_RESOLVERS;
+use clap::builder::PossibleValuesParser;
 use clap::{Arg, ArgAction, Command};
 
 /// Command-line interface configuration.
@@ -26,6 +29,24 @@ pub struct Cli {
     pub update_hash: bool,
     /// Initialize a .cddrc.json config file with current cycles as baseline.
     pub init: bool,
+    /// Only report cycles where every import is read during module evaluation.
+    pub eager_only: bool,
+    /// How test-framework mock edges are handled. None means not specified on CLI.
+    pub mock_imports: Option<MockImports>,
+    /// Include worker edges in cycle detection.
+    pub include_workers: bool,
+    /// How `moduleSuffixes` platform variants resolve. None means not specified on CLI.
+    pub module_suffixes: Option<ModuleSuffixes>,
+    /// A target platform's `moduleSuffixes`. None means not specified on CLI.
+    pub platform_suffixes: Option<PlatformSuffixes>,
+    /// Conditions for package.json `exports`/`imports`. None means not specified on CLI.
+    pub conditions: Option<Vec<String>>,
+    /// Record third-party packages as external nodes.
+    pub external_nodes: bool,
+    /// Resolver chain, in order. None means not specified on CLI.
+    pub resolvers: Option<Vec<String>>,
+    /// Exit with an error when relative or aliased imports don't resolve.
+    pub fail_on_unresolved: bool,
 }
 
 /// Parses command-line arguments and returns a [`Cli`] configuration.
@@ -75,13 +96,73 @@ pub fn parse_args() -> Cli {
             Arg::new("ignore_type_imports")
                 .short('t')
                 .long("ignore-type-imports")
-                .help("Ignore type-only imports (import type { Foo }). These are erased at compile time and don't cause runtime cycles.")
+                .help("Ignore type-only imports (import type { Foo }), and value imports only used as types. These are erased at compile time and don't cause runtime cycles.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("eager_only")
+                .long("eager-only")
+                .help("Only report cycles where every import is read while the module is evaluated (e.g. `extends`, top-level calls). These are the cycles that can fail at runtime.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("mock_imports")
+                .long("mock-imports")
+                .help("How to handle jest.mock()/vi.mock() edges: count them like imports, exclude them, or report their cycles separately (default)")
+                .value_parser(["count", "exclude", "separate"])
+                .num_args(1),
+        )
+        .arg(
+            Arg::new("include_workers")
+                .long("include-workers")
+                .help("Include `new Worker(new URL(...))` edges in cycle detection. Workers run in a separate realm, so they're excluded by default.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("module_suffixes")
+                .long("module-suffixes")
+                .help("How imports resolve when tsconfig sets `moduleSuffixes`: to the first variant that exists, like tsc (default), or to every platform variant")
+                .value_parser(["first", "all"])
+                .num_args(1),
+        )
+        .arg(
+            Arg::new("platform_suffixes")
+                .long("platform-suffixes")
+                .help("Comma-separated `moduleSuffixes` of one target platform, used instead of the tsconfig's (e.g. `.android,.native,`; the trailing comma is the empty suffix)")
+                .value_parser(|value: &str| value.parse::<PlatformSuffixes>())
+                .num_args(1),
+        )
+        .arg(
+            Arg::new("conditions")
+                .long("conditions")
+                .help("Comma-separated conditions for package.json exports/imports, matched in the package's declaration order (default: import,require; `default` always matches)")
+                .value_delimiter(',')
+                .num_args(1),
+        )
+        .arg(
+            Arg::new("external_nodes")
+                .long("external-nodes")
+                .help("Record third-party packages imported from node_modules as external nodes. They can't be part of a cycle, but are listed in the output.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("resolvers")
+                .long("resolvers")
+                .help("Comma-separated resolvers to try, in order (default: package-imports,relative,aliases,tsconfig,bundler-aliases,workspace,node-modules). Resolvers left out are disabled.")
+                .value_parser(PossibleValuesParser::new(DEFAULT_RESOLVERS.iter().copied()))
+                .value_delimiter(',')
+                .num_args(1),
+        )
+        .arg(
+            Arg::new("fail_on_unresolved")
+                .long("fail-on-unresolved")
+                .help("Exit with an error when relative or aliased imports don't resolve to a file")
                 .action(ArgAction::SetTrue),
         )
         .arg(
             Arg::new("tsconfig")
                 .long("tsconfig")
-                .help("Path to tsconfig.json (auto-detected by default)")
+                .help("Path to tsconfig.json used for every file (by default, the nearest tsconfig.json above each file is used)")
                 .num_args(1),
         )
         .arg(
@@ -165,5 +246,33 @@ pub fn parse_args() -> Cli {
         allowlist: matches.get_one::<String>("allowlist").cloned(),
         update_hash: *matches.get_one::<bool>("update_hash").unwrap_or(&false),
         init: *matches.get_one::<bool>("init").unwrap_or(&false),
+        eager_only: *matches.get_one::<bool>("eager_only").unwrap_or(&false),
+        mock_imports: matches
+            .get_one::<String>("mock_imports")
+            .map(|mode| match mode.as_str() {
+                "count" => MockImports::Count,
+                "exclude" => MockImports::Exclude,
+                _ => MockImports::Separate,
+            }),
+        include_workers: *matches.get_one::<bool>("include_workers").unwrap_or(&false),
+        module_suffixes: matches.get_one::<String>("module_suffixes").map(|mode| {
+            match mode.as_str() {
+                "all" => ModuleSuffixes::All,
+                _ => ModuleSuffixes::First,
+            }
+        }),
+        platform_suffixes: matches
+            .get_one::<PlatformSuffixes>("platform_suffixes")
+            .cloned(),
+        conditions: matches
+            .get_many::<String>("conditions")
+            .map(|values| values.cloned().collect()),
+        external_nodes: *matches.get_one::<bool>("external_nodes").unwrap_or(&false),
+        resolvers: matches
+            .get_many::<String>("resolvers")
+            .map(|values| values.cloned().collect()),
+        fail_on_unresolved: *matches
+            .get_one::<bool>("fail_on_unresolved")
+            .unwrap_or(&false),
     }
 }
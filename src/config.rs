@@ -1,7 +1,11 @@
-use crate::graph::CycleInfo;
+use crate::aliases::AliasMap;
+use crate::graph::{CycleInfo, MockImports, ModuleSuffixes, PlatformSuffixes};
+use crate::resolver::{builtin_resolver, DEFAULT_RESOLVERS};
 use crate::utils::relative_path_string;
+use crate::workspace::DEFAULT_CONDITIONS;
 use log::debug;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 /// An allowed cycle that won't cause CI failure
@@ -35,6 +39,39 @@ pub struct CddConfig {
     /// Cycles that are allowed (won't cause CI failure).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub allowed_cycles: Option<Vec<AllowedCycle>>,
+    /// Only report cycles where every import is read during module evaluation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eager_only: Option<bool>,
+    /// How test-framework mock edges are handled ("count", "exclude" or "separate").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mock_imports: Option<MockImports>,
+    /// Whether worker edges take part in cycle detection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_workers: Option<bool>,
+    /// Whether `moduleSuffixes` resolve to the first variant ("first") or every variant ("all").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub module_suffixes: Option<ModuleSuffixes>,
+    /// A target platform's `moduleSuffixes` (e.g. [".android", ".native", ""]), used instead of the tsconfig's.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platform_suffixes: Option<PlatformSuffixes>,
+    /// Conditions for package.json `exports`/`imports` (e.g. ["development", "browser", "import"]).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conditions: Option<Vec<String>>,
+    /// Whether third-party packages in `node_modules` are recorded as external nodes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_nodes: Option<bool>,
+    /// Import aliases (e.g. {"@": "./src"}); relative paths are resolved against the config file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aliases: Option<BTreeMap<String, String>>,
+    /// Resolvers to try, in order (e.g. ["relative", "tsconfig", "workspace"]).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolvers: Option<Vec<String>>,
+    /// Whether relative or aliased imports that don't resolve fail the run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fail_on_unresolved: Option<bool>,
+    /// Directory of the config file, set when it's loaded.
+    #[serde(skip)]
+    pub dir: Option<PathBuf>,
 }
 
 const CONFIG_FILE_NAMES: &[&str] = &[".cddrc.json", "cdd.config.json"];
@@ -68,8 +105,11 @@ pub fn find_config(start_dir: &Path) -> Option<(PathBuf, CddConfig)> {
 /// Loads and parses a config file.
 fn load_config(path: &PathBuf) -> Option<CddConfig> {
     let content = std::fs::read_to_string(path).ok()?;
-    match serde_json::from_str(&content) {
-        Ok(config) => Some(config),
+    match serde_json::from_str::<CddConfig>(&content) {
+        Ok(mut config) => {
+            config.dir = path.parent().map(Path::to_path_buf);
+            Some(config)
+        }
         Err(e) => {
             log::warn!("Failed to parse config file '{}': {}", path.display(), e);
             None
@@ -167,6 +207,27 @@ pub fn init_config(dir: &Path, cycles: &[CycleInfo]) -> Result<PathBuf, String>
     Ok(config_path)
 }
 
+/// Options given on the command line that can also come from a config file.
+/// `None`/`false` means "not specified on the CLI".
+#[derive(Debug, Default)]
+pub struct CliOptions {
+    pub exclude: Vec<String>,
+    pub ignore_type_imports: bool,
+    pub expected_cycles: Option<usize>,
+    pub tsconfig_path: Option<String>,
+    pub expected_hash: Option<String>,
+    pub allowlist_path: Option<String>,
+    pub eager_only: bool,
+    pub mock_imports: Option<MockImports>,
+    pub include_workers: bool,
+    pub module_suffixes: Option<ModuleSuffixes>,
+    pub platform_suffixes: Option<PlatformSuffixes>,
+    pub conditions: Option<Vec<String>>,
+    pub external_nodes: bool,
+    pub resolvers: Option<Vec<String>>,
+    pub fail_on_unresolved: bool,
+}
+
 /// Merged configuration from CLI arguments and config file.
 /// CLI arguments take precedence over config file values.
 #[derive(Debug)]
@@ -177,52 +238,91 @@ pub struct MergedConfig {
     pub tsconfig_path: Option<String>,
     pub expected_hash: Option<String>,
     pub allowed_cycles: Vec<AllowedCycle>,
+    pub eager_only: bool,
+    pub mock_imports: MockImports,
+    pub include_workers: bool,
+    pub module_suffixes: ModuleSuffixes,
+    pub platform_suffixes: Option<PlatformSuffixes>,
+    pub conditions: Vec<String>,
+    pub external_nodes: bool,
+    pub aliases: AliasMap,
+    pub resolvers: Vec<String>,
+    pub fail_on_unresolved: bool,
 }
 
 impl MergedConfig {
     /// Creates a merged config from CLI arguments and an optional config file.
     /// CLI arguments always take precedence when specified.
-    pub fn new(
-        cli_exclude: Vec<String>,
-        cli_ignore_type_imports: bool,
-        cli_expected_cycles: Option<usize>,
-        cli_tsconfig_path: Option<String>,
-        cli_expected_hash: Option<String>,
-        cli_allowlist_path: Option<String>,
-        file_config: Option<CddConfig>,
-    ) -> Self {
+    pub fn new(cli: CliOptions, file_config: Option<CddConfig>) -> Self {
         let file_config = file_config.unwrap_or_default();
 
         // For exclude: merge CLI and config file (CLI additions are added to config file list)
         let mut exclude = file_config.exclude.unwrap_or_default();
-        for item in cli_exclude {
+        for item in cli.exclude {
             if !exclude.contains(&item) {
                 exclude.push(item);
             }
         }
 
         // For booleans: CLI takes precedence if true, otherwise use config file
-        let ignore_type_imports = if cli_ignore_type_imports {
+        let ignore_type_imports = if cli.ignore_type_imports {
             true
         } else {
             file_config.ignore_type_imports.unwrap_or(false)
         };
+        let eager_only = cli.eager_only || file_config.eager_only.unwrap_or(false);
+        let include_workers = cli.include_workers || file_config.include_workers.unwrap_or(false);
+        let external_nodes = cli.external_nodes || file_config.external_nodes.unwrap_or(false);
+        let fail_on_unresolved =
+            cli.fail_on_unresolved || file_config.fail_on_unresolved.unwrap_or(false);
+
+        let aliases = AliasMap::from_config(
+            &file_config.aliases.unwrap_or_default(),
+            file_config.dir.as_deref().unwrap_or(Path::new(".")),
+        );
+
+        let mut resolvers = cli
+            .resolvers
+            .or(file_config.resolvers)
+            .unwrap_or_else(|| DEFAULT_RESOLVERS.iter().map(|r| r.to_string()).collect());
+        resolvers.retain(|name| {
+            let known = builtin_resolver(name).is_some();
+            if !known {
+                log::warn!("Ignoring unknown resolver '{}'", name);
+            }
+            known
+        });
+
+        let mock_imports = cli
+            .mock_imports
+            .or(file_config.mock_imports)
+            .unwrap_or_default();
+        let module_suffixes = cli
+            .module_suffixes
+            .or(file_config.module_suffixes)
+            .unwrap_or_default();
+        let platform_suffixes = cli.platform_suffixes.or(file_config.platform_suffixes);
+        let conditions = cli
+            .conditions
+            .or(file_config.conditions)
+            .unwrap_or_else(|| DEFAULT_CONDITIONS.iter().map(|c| c.to_string()).collect());
 
         // For expected_cycles: CLI takes precedence if specified, otherwise use config file
-        let expected_cycles = cli_expected_cycles
+        let expected_cycles = cli
+            .expected_cycles
             .or(file_config.expected_cycles)
             .unwrap_or(0);
 
-        let tsconfig_path = cli_tsconfig_path.or(file_config.tsconfig_path);
+        let tsconfig_path = cli.tsconfig_path.or(file_config.tsconfig_path);
 
         // For expected_hash: CLI takes precedence if specified
-        let expected_hash = cli_expected_hash.or(file_config.expected_hash);
+        let expected_hash = cli.expected_hash.or(file_config.expected_hash);
 
         // For allowed_cycles: merge CLI allowlist file with config file
         let mut allowed_cycles = file_config.allowed_cycles.unwrap_or_default();
 
         // Load additional allowlist from file if specified via CLI
-        if let Some(allowlist_path) = cli_allowlist_path {
+        if let Some(allowlist_path) = cli.allowlist_path {
             let path = Path::new(&allowlist_path);
             if let Some(file_allowlist) = load_allowlist(path) {
                 allowed_cycles.extend(file_allowlist);
@@ -238,6 +338,16 @@ impl MergedConfig {
             tsconfig_path,
             expected_hash,
             allowed_cycles,
+            eager_only,
+            mock_imports,
+            include_workers,
+            module_suffixes,
+            platform_suffixes,
+            conditions,
+            external_nodes,
+            aliases,
+            resolvers,
+            fail_on_unresolved,
         }
     }
 }
@@ -245,6 +355,7 @@ impl MergedConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::aliases::AliasTarget;
     use std::io::Write;
     use tempfile::TempDir;
 
@@ -290,6 +401,32 @@ mod tests {
         assert!(content.contains("expected_cycles"));
     }
 
+    #[test]
+    fn test_module_suffixes_and_platform_suffixes() {
+        let config: CddConfig = serde_json::from_str(r#"{"module_suffixes": "all"}"#).unwrap();
+        assert_eq!(config.module_suffixes, Some(ModuleSuffixes::All));
+        assert!(serde_json::from_str::<CddConfig>(r#"{"module_suffixes": "al"}"#).is_err());
+
+        let config: CddConfig =
+            serde_json::from_str(r#"{"platform_suffixes": [".android", ".native", ""]}"#).unwrap();
+        let expected: PlatformSuffixes = ".android,.native,".parse().unwrap();
+        assert_eq!(config.platform_suffixes.as_ref(), Some(&expected));
+        assert_eq!(
+            expected.as_slice(),
+            [".android".to_string(), ".native".to_string(), String::new()]
+        );
+        assert_eq!(
+            serde_json::to_string(&config).unwrap(),
+            r#"{"platform_suffixes":[".android",".native",""]}"#
+        );
+
+        // Every suffix starts with `.` or is empty
+        assert!(
+            serde_json::from_str::<CddConfig>(r#"{"platform_suffixes": ["android"]}"#).is_err()
+        );
+        assert!("android,.native".parse::<PlatformSuffixes>().is_err());
+    }
+
     #[test]
     fn test_merged_config_cli_precedence() {
         let file_config = CddConfig {
@@ -299,16 +436,35 @@ mod tests {
         };
 
         let merged = MergedConfig::new(
-            vec![],
-            false,
-            Some(10),
-            None,
-            Some("newhash".to_string()),
-            None,
+            CliOptions {
+                expected_cycles: Some(10),
+                expected_hash: Some("newhash".to_string()),
+                ..Default::default()
+            },
             Some(file_config),
         );
 
         assert_eq!(merged.expected_cycles, 10);
         assert_eq!(merged.expected_hash, Some("newhash".to_string()));
     }
+
+    #[test]
+    fn test_aliases_relative_to_config_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let nested = temp_dir.path().join("apps/web");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(
+            temp_dir.path().join(".cddrc.json"),
+            r#"{"aliases": {"@shared": "./shared"}}"#,
+        )
+        .unwrap();
+
+        let (_, config) = find_config(&nested).unwrap();
+        let merged = MergedConfig::new(CliOptions::default(), Some(config));
+
+        assert_eq!(
+            merged.aliases.resolve("@shared/date"),
+            vec![AliasTarget::Path(temp_dir.path().join("shared/date"))]
+        );
+    }
 }
@@ -1,6 +1,8 @@
+use crate::utils::has_supported_extension;
 use log::debug;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use walkdir::{DirEntry, WalkDir};
 
 /// Normalizes a path by resolving it to an absolute path
@@ -9,10 +11,59 @@ pub fn normalize_path(path: &Path) -> PathBuf {
     path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
 }
 
+/// Finds, for each file, the value of the nearest directory at or above it that
+/// has one (e.g. its nearest tsconfig.json). Results are cached per directory, so
+/// each directory is only probed once.
+#[derive(Debug)]
+pub struct NearestAncestorCache<T> {
+    /// Directory -> value of the nearest directory at or above it
+    by_dir: Mutex<HashMap<PathBuf, Option<T>>>,
+}
+
+impl<T> Default for NearestAncestorCache<T> {
+    fn default() -> Self {
+        Self {
+            by_dir: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<T: Clone> NearestAncestorCache<T> {
+    /// Walks up from the file's directory until `load` returns a value for a
+    /// directory. `load` is only called for directories not seen before.
+    pub fn get(&self, file: &Path, load: impl Fn(&Path) -> Option<T>) -> Option<T> {
+        let mut by_dir = self.by_dir.lock().unwrap_or_else(|e| e.into_inner());
+        let mut visited = Vec::new();
+        let mut current = file.parent();
+
+        let found = loop {
+            let Some(dir) = current else {
+                break None;
+            };
+            if let Some(cached) = by_dir.get(dir) {
+                break cached.clone();
+            }
+            visited.push(dir.to_path_buf());
+
+            if let Some(value) = load(dir) {
+                break Some(value);
+            }
+            current = dir.parent();
+        };
+
+        // Every directory between the file and the one with a value shares the result
+        for dir in visited {
+            by_dir.insert(dir, found.clone());
+        }
+        found
+    }
+}
+
 /// Collects all TypeScript and JavaScript files from a directory.
 ///
 /// Walks the directory tree, filtering out excluded directories and
-/// returning only files with supported extensions (.ts, .tsx, .js, .jsx, .cjs, .mjs).
+/// returning only files with supported extensions (.ts, .tsx, .mts, .cts, .js, .jsx, .cjs, .mjs)
+/// and single-file components (.vue, .svelte, .astro).
 pub fn collect_files(dir: &str, excludes: &[String]) -> Vec<PathBuf> {
     let exclude_set: HashSet<_> = excludes.iter().collect();
     let base_dir = PathBuf::from(dir)
@@ -23,13 +74,7 @@ pub fn collect_files(dir: &str, excludes: &[String]) -> Vec<PathBuf> {
         .into_iter()
         .filter_entry(|e| should_include(e, &exclude_set))
         .filter_map(|e| e.ok())
-        .filter(|entry| {
-            entry.file_type().is_file()
-                && matches!(
-                    entry.path().extension().and_then(|ext| ext.to_str()),
-                    Some("ts" | "tsx" | "js" | "jsx" | "cjs" | "mjs")
-                )
-        })
+        .filter(|entry| entry.file_type().is_file() && has_supported_extension(entry.path()))
         .map(|e| {
             let absolute_path = e
                 .path()
@@ -53,3 +98,27 @@ fn should_include(entry: &DirEntry, exclude_set: &HashSet<&String>) -> bool {
     }
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_nearest_ancestor_cache_probes_each_directory_once() {
+        let cache = NearestAncestorCache::default();
+        let probes = Cell::new(0);
+        let load = |dir: &Path| {
+            probes.set(probes.get() + 1);
+            (dir == Path::new("/repo/app")).then(|| dir.to_path_buf())
+        };
+
+        let expected = Some(PathBuf::from("/repo/app"));
+        assert_eq!(cache.get(Path::new("/repo/app/src/a/x.ts"), load), expected);
+        assert_eq!(probes.get(), 3);
+        assert_eq!(cache.get(Path::new("/repo/app/src/b.ts"), load), expected);
+        assert_eq!(cache.get(Path::new("/repo/app/src/a/y.ts"), load), expected);
+        assert_eq!(probes.get(), 3);
+        assert_eq!(cache.get(Path::new("/repo/other/z.ts"), load), None);
+    }
+}
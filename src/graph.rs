@@ -1,24 +1,165 @@
+use crate::aliases::AliasMap;
 use crate::filesystem::normalize_path;
-use crate::parser::{get_imports_from_file, ImportInfo, ParserOptions};
-use crate::tsconfig::PathAliases;
-use crate::utils::{hash_strings, relative_path_string, EXTENSIONS};
-use crate::workspace::Workspace;
-
+use crate::node_modules::NodeModulesTarget;
+use crate::parser::{
+    get_imports_from_file, Evaluation, GlobImport, ImportInfo, ImportKind, ImportedBinding,
+    ParserOptions,
+};
+use crate::resolver::{
+    resolvers_from_names, strip_query, ResolveContext, ResolveRequest, Resolved, Resolver,
+    DEFAULT_RESOLVERS,
+};
+use crate::tsconfig::TsConfigCache;
+use crate::utils::{has_supported_extension, hash_strings, relative_path_string};
+use crate::workspace::{Workspace, DEFAULT_CONDITIONS};
+
+use glob::{MatchOptions, Pattern};
 use log::{debug, warn};
 use petgraph::algo::kosaraju_scc;
 use petgraph::graph::NodeIndex;
 use petgraph::visit::EdgeRef;
 use petgraph::Graph;
 use rayon::prelude::*;
-use serde::Serialize;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Information stored on each edge in the dependency graph
 #[derive(Debug, Clone, Serialize)]
 pub struct EdgeInfo {
     /// The import information that created this edge
     pub import: ImportInfo,
+    /// The resolver that produced this edge (`glob` for glob imports)
+    pub resolver: &'static str,
+}
+
+/// How test-framework mock edges (`jest.mock('./db')`) take part in cycle detection
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MockImports {
+    /// Treat mocks like any other import
+    Count,
+    /// Ignore mock edges entirely
+    Exclude,
+    /// Report cycles that need a mock edge separately, without counting them
+    #[default]
+    Separate,
+}
+
+/// Which files an import resolves to when the tsconfig sets `moduleSuffixes`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModuleSuffixes {
+    /// Resolve like `tsc`: the first suffix that exists wins (one target platform)
+    #[default]
+    First,
+    /// Add an edge to every platform variant that exists
+    All,
+}
+
+/// The `moduleSuffixes` of one target platform (e.g. `[".android", ".native", ""]`),
+/// used instead of the tsconfig's. Each suffix starts with `.` or is empty.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "Vec<String>", into = "Vec<String>")]
+pub struct PlatformSuffixes(Vec<String>);
+
+impl PlatformSuffixes {
+    pub fn as_slice(&self) -> &[String] {
+        &self.0
+    }
+}
+
+impl TryFrom<Vec<String>> for PlatformSuffixes {
+    type Error = String;
+
+    fn try_from(suffixes: Vec<String>) -> Result<Self, Self::Error> {
+        match suffixes
+            .iter()
+            .find(|suffix| !suffix.is_empty() && !suffix.starts_with('.'))
+        {
+            Some(suffix) => Err(format!(
+                "invalid module suffix '{}': suffixes start with '.' or are empty",
+                suffix
+            )),
+            None => Ok(Self(suffixes)),
+        }
+    }
+}
+
+impl From<PlatformSuffixes> for Vec<String> {
+    fn from(suffixes: PlatformSuffixes) -> Self {
+        suffixes.0
+    }
+}
+
+impl std::str::FromStr for PlatformSuffixes {
+    type Err = String;
+
+    /// Parses a comma-separated list (`.android,.native,`). A trailing comma
+    /// stands for the empty suffix, as in the tsconfig list.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        value
+            .split(',')
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+            .try_into()
+    }
+}
+
+/// Options controlling how imports are resolved to files
+#[derive(Debug, Clone)]
+pub struct ResolveOptions {
+    /// How `moduleSuffixes` variants are turned into edges
+    pub module_suffixes: ModuleSuffixes,
+    /// A target platform's suffixes, used instead of every tsconfig's `moduleSuffixes`
+    pub platform_suffixes: Option<PlatformSuffixes>,
+    /// Active conditions for package.json `exports` and `imports` (`default` always matches)
+    pub conditions: Vec<String>,
+    /// Record third-party packages found in `node_modules` as external nodes
+    pub external_nodes: bool,
+    /// Aliases from the config file, tried before tsconfig `paths`
+    pub aliases: AliasMap,
+    /// The resolver chain, tried in order until one finds a file
+    pub resolvers: Vec<Arc<dyn Resolver>>,
+}
+
+impl Default for ResolveOptions {
+    fn default() -> Self {
+        Self {
+            module_suffixes: ModuleSuffixes::default(),
+            platform_suffixes: None,
+            conditions: DEFAULT_CONDITIONS.iter().map(|c| c.to_string()).collect(),
+            external_nodes: false,
+            aliases: AliasMap::default(),
+            resolvers: resolvers_from_names(DEFAULT_RESOLVERS),
+        }
+    }
+}
+
+/// Options controlling which edges take part in cycle detection
+#[derive(Debug, Clone, Default)]
+pub struct CycleOptions {
+    /// Only report cycles made entirely of eager edges (real runtime hazards)
+    pub eager_only: bool,
+    /// How mock edges are handled
+    pub mock_imports: MockImports,
+    /// Follow worker edges too (workers run in a separate realm, so they're skipped by default)
+    pub include_workers: bool,
+}
+
+impl CycleOptions {
+    /// Whether an edge should be considered when looking for cycles
+    fn includes_edge(&self, edge: &EdgeInfo) -> bool {
+        if edge.import.kind == ImportKind::Mock && self.mock_imports != MockImports::Count {
+            return false;
+        }
+        if edge.import.kind == ImportKind::Worker && !self.include_workers {
+            return false;
+        }
+        !self.eager_only || edge.import.evaluation == Evaluation::Eager
+    }
 }
 
 /// A single edge in a cycle, with file and import information
@@ -32,6 +173,16 @@ pub struct CycleEdge {
     pub line: u32,
     /// The full import text
     pub import_text: String,
+    /// The kind of import that created this edge
+    pub kind: ImportKind,
+    /// The bindings that cross this edge
+    pub specifiers: Vec<ImportedBinding>,
+    /// Whether the bindings are read during module evaluation
+    pub evaluation: Evaluation,
+    /// Whether the edge was guessed from a computed import specifier
+    pub is_speculative: bool,
+    /// The resolver that produced this edge
+    pub resolver: &'static str,
 }
 
 /// Information about a detected cycle
@@ -80,20 +231,97 @@ impl CycleInfo {
     }
 }
 
-/// Builds the dependency graph from a list of files.
-/// Handles relative imports, path aliases, and workspace packages.
+/// An import that resolves to a file outside the scanned directory, such as
+/// another workspace package when scanning a single package. These aren't
+/// followed, since the target isn't part of the graph.
+#[derive(Debug, Clone, Serialize)]
+pub struct BoundaryEdge {
+    /// Source file of the import
+    pub from_file: PathBuf,
+    /// The file outside the scanned directory
+    pub to_file: PathBuf,
+    /// Line number of the import statement (1-indexed)
+    pub line: u32,
+    /// The full import text
+    pub import_text: String,
+    /// The resolver that produced this edge
+    pub resolver: &'static str,
+}
+
+/// Why an unresolved import is worth reporting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnresolvedKind {
+    /// A relative import pointing at a missing file (`./UserServce`)
+    Relative,
+    /// A matching alias (config, tsconfig `paths`, bundler or `#` imports) whose
+    /// targets don't exist
+    Alias,
+    /// A workspace package that doesn't export the imported path
+    Package,
+    /// A glob pattern that isn't expanded, because it's aliased or root-relative
+    /// (`import.meta.glob('/src/pages/*.ts')`) and depends on the bundler's config
+    Glob,
+}
+
+impl UnresolvedKind {
+    /// Whether the import is broken for sure, so `fail_on_unresolved` fails the run.
+    /// A workspace package may only export the path once it's built.
+    pub fn is_broken(self) -> bool {
+        matches!(self, UnresolvedKind::Relative | UnresolvedKind::Alias)
+    }
+}
+
+/// An import no resolver found a file for. Built-ins and third-party packages
+/// aren't recorded, since they're external on purpose.
+#[derive(Debug, Clone, Serialize)]
+pub struct UnresolvedImport {
+    /// Source file of the import
+    pub from_file: PathBuf,
+    /// Line number of the import statement (1-indexed)
+    pub line: u32,
+    /// The import specifier that didn't resolve
+    pub specifier: String,
+    /// The full import text
+    pub import_text: String,
+    /// What kind of import it is
+    pub kind: UnresolvedKind,
+}
+
+/// The dependency graph of the scanned files.
+#[derive(Debug, Default)]
+pub struct DependencyGraph {
+    /// Files and the imports between them
+    pub graph: Graph<PathBuf, EdgeInfo>,
+    /// Imports that leave the scanned directory
+    pub boundary_edges: Vec<BoundaryEdge>,
+    /// Names of the third-party packages recorded as external nodes, sorted and
+    /// deduplicated. Each installed copy (real directory) of a package is its own
+    /// node; they have no outgoing edges, so they never take part in cycles.
+    pub external_packages: Vec<String>,
+    /// Imports that should point at a project file but don't resolve, sorted by file and line
+    pub unresolved_imports: Vec<UnresolvedImport>,
+}
+
+/// Builds the dependency graph from a list of files under `root`.
+/// Handles relative imports, path aliases, workspace and `node_modules` packages,
+/// and records imports that should resolve but don't.
 /// Parses files in parallel for performance.
 pub fn build_dependency_graph(
     files: &[PathBuf],
+    root: &Path,
     options: &ParserOptions,
-    path_aliases: Option<&PathAliases>,
+    resolve_options: &ResolveOptions,
+    tsconfigs: Option<&TsConfigCache>,
     workspace: Option<&Workspace>,
-) -> Graph<PathBuf, EdgeInfo> {
+) -> DependencyGraph {
     let mut graph = Graph::new();
+    let mut boundary_edges = Vec::new();
+    let mut unresolved_imports = Vec::new();
     let mut node_indices = HashMap::new();
-
-    // Convert static extensions to owned strings for compatibility
-    let extensions: Vec<String> = EXTENSIONS.iter().map(|s| s.to_string()).collect();
+    // Keyed by real package directory, so two installed versions stay apart
+    let mut external_nodes: HashMap<PathBuf, (String, NodeIndex)> = HashMap::new();
+    let ctx = ResolveContext::new(resolve_options, workspace);
 
     // Insert all files as nodes
     for file in files {
@@ -114,116 +342,278 @@ pub fn build_dependency_graph(
     // Build edges from the collected imports (must be sequential for graph mutation)
     for (file, imports) in file_imports {
         debug!("Processing file: {:?}", file);
+        // Each file resolves aliases with the tsconfig that owns it
+        let path_aliases = tsconfigs.and_then(|t| t.for_file(file));
         for import in imports {
-            if let Some(resolved) =
-                resolve_import(file, &import.source, &extensions, path_aliases, workspace)
-            {
+            // Glob imports get one edge per matched file, all pointing at the call site
+            if let Some(glob) = &import.glob {
+                let from_idx = node_indices[file];
+                let (matched, skipped) = expand_glob_import(file, glob, files);
+                for pattern in skipped {
+                    debug!(
+                        "Skipped non-relative glob pattern '{}' in {:?}",
+                        pattern, file
+                    );
+                    unresolved_imports.push(UnresolvedImport {
+                        from_file: file.clone(),
+                        line: import.line,
+                        specifier: pattern.to_string(),
+                        import_text: import.import_text.clone(),
+                        kind: UnresolvedKind::Glob,
+                    });
+                }
+                for matched in matched {
+                    debug!("Added glob edge: {:?} -> {:?}", file, matched);
+                    let to_idx = node_indices[matched];
+                    graph.add_edge(
+                        from_idx,
+                        to_idx,
+                        EdgeInfo {
+                            import: import.clone(),
+                            resolver: "glob",
+                        },
+                    );
+                }
+                continue;
+            }
+
+            // Bundler queries (`?worker`) and hashes don't name a different file
+            let specifier = strip_query(&import.source);
+            let request = ResolveRequest::new(file, specifier, path_aliases.as_deref());
+            let resolved = ctx.resolve(&request);
+            // External nodes come from the `node-modules` resolver, so only with it in the chain
+            if resolved.is_none() && resolve_options.external_nodes && ctx.uses("node-modules") {
+                if let Some(NodeModulesTarget::External { name, path }) =
+                    ctx.node_modules
+                        .resolve(file, specifier, &resolve_options.conditions)
+                {
+                    let (_, to_idx) = *external_nodes
+                        .entry(path.clone())
+                        .or_insert_with(|| (name, graph.add_node(path)));
+                    graph.add_edge(
+                        node_indices[file],
+                        to_idx,
+                        EdgeInfo {
+                            import: import.clone(),
+                            resolver: "node-modules",
+                        },
+                    );
+                    debug!("Added external edge: {:?} -> '{}'", file, import.source);
+                    continue;
+                }
+            }
+            let Some(Resolved { paths, resolver }) = resolved else {
+                match ctx.classify_unresolved(&request) {
+                    Some(kind) => {
+                        debug!(
+                            "Could not resolve {:?} import '{}' from {:?}",
+                            kind, import.source, file
+                        );
+                        unresolved_imports.push(UnresolvedImport {
+                            from_file: file.clone(),
+                            line: import.line,
+                            specifier: import.source.clone(),
+                            import_text: import.import_text.clone(),
+                            kind,
+                        });
+                    }
+                    None => debug!(
+                        "Skipped external import '{}' from {:?}",
+                        import.source, file
+                    ),
+                }
+                continue;
+            };
+            // Usually one target; one per platform variant with `ModuleSuffixes::All`
+            for resolved in paths {
                 if let Some(&to_idx) = node_indices.get(&resolved) {
                     let from_idx = node_indices[file];
-                    graph.add_edge(from_idx, to_idx, EdgeInfo { import });
-                    debug!("Added edge: {:?} -> {:?}", file, resolved);
+                    graph.add_edge(
+                        from_idx,
+                        to_idx,
+                        EdgeInfo {
+                            import: import.clone(),
+                            resolver,
+                        },
+                    );
+                    debug!("Added edge: {:?} -> {:?} ({})", file, resolved, resolver);
+                } else if !resolved.starts_with(root) {
+                    debug!("Added boundary edge: {:?} -> {:?}", file, resolved);
+                    boundary_edges.push(BoundaryEdge {
+                        from_file: file.clone(),
+                        to_file: resolved,
+                        line: import.line,
+                        import_text: import.import_text.clone(),
+                        resolver,
+                    });
+                } else if !has_supported_extension(&resolved) {
+                    debug!("Skipped asset import: {:?} -> {:?}", file, resolved);
                 } else {
                     warn!("Resolved import not found in node_indices: {:?}", resolved);
                 }
-            } else {
-                debug!(
-                    "Skipped external or unresolved import '{}' from {:?}",
-                    import.source, file
-                );
             }
         }
     }
 
-    graph
+    let mut external_packages: Vec<String> =
+        external_nodes.into_values().map(|(name, _)| name).collect();
+    external_packages.sort();
+    external_packages.dedup();
+    unresolved_imports.sort_by(|a, b| (&a.from_file, a.line).cmp(&(&b.from_file, b.line)));
+
+    DependencyGraph {
+        graph,
+        boundary_edges,
+        external_packages,
+        unresolved_imports,
+    }
 }
 
-/// Resolves an import to an absolute, normalized PathBuf.
-/// Handles relative imports, path aliases, and workspace packages.
-/// Returns `None` if the import cannot be resolved.
-fn resolve_import(
+/// Returns the files matched by a glob import, excluding the importing file itself,
+/// and the patterns that can't be expanded.
+/// Patterns are resolved relative to the importing file; `!` patterns exclude matches.
+fn expand_glob_import<'a, 'g>(
     base: &Path,
-    import: &str,
-    extensions: &[String],
-    path_aliases: Option<&PathAliases>,
-    workspace: Option<&Workspace>,
-) -> Option<PathBuf> {
-    debug!("Attempting to resolve import: '{}' from {:?}", import, base);
-
-    // Try relative imports first
-    if import.starts_with('.') {
-        let candidate = base.parent()?.join(import);
-        if let Some(resolved) = check_candidates(candidate, extensions) {
-            return Some(resolved);
-        }
-    }
+    glob: &'g GlobImport,
+    files: &'a [PathBuf],
+) -> (Vec<&'a PathBuf>, Vec<&'g str>) {
+    let Some(dir) = base.parent() else {
+        return (vec![], vec![]);
+    };
 
-    // Try path aliases if configured
-    if let Some(aliases) = path_aliases {
-        if let Some(candidate) = aliases.resolve(import) {
-            if let Some(resolved) = check_candidates(candidate, extensions) {
-                return Some(resolved);
-            }
+    let filter = match glob.filter.as_deref().map(Regex::new) {
+        Some(Ok(re)) => Some(re),
+        Some(Err(err)) => {
+            warn!(
+                "Skipping glob import in {:?}: unsupported filter: {}",
+                base, err
+            );
+            return (vec![], vec![]);
         }
-    }
+        None => None,
+    };
 
-    // Try workspace package resolution
-    if let Some(ws) = workspace {
-        if let Some(resolved) = ws.resolve(import) {
-            let normalized = normalize_path(&resolved);
-            debug!("Resolved workspace import '{}' to {:?}", import, normalized);
-            return Some(normalized);
+    let mut include = Vec::new();
+    let mut exclude = Vec::new();
+    let mut skipped = Vec::new();
+    for pattern in &glob.patterns {
+        let (negated, pattern) = match pattern.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, pattern.as_str()),
+        };
+        // Aliased or root-relative patterns depend on bundler config we don't read
+        if !negated && !pattern.starts_with('.') {
+            skipped.push(pattern);
+            continue;
+        }
+        for expanded in expand_braces(pattern) {
+            match GlobMatcher::new(dir, &expanded) {
+                Some(matcher) if negated => exclude.push(matcher),
+                Some(matcher) => include.push(matcher),
+                None => warn!("Invalid glob pattern '{}' in {:?}", expanded, base),
+            }
         }
     }
 
-    None
+    let matched = files
+        .iter()
+        .filter(|file| file.as_path() != base)
+        .filter(|file| {
+            include.iter().any(|m| match m.relative_match(file) {
+                Some(relative) => filter
+                    .as_ref()
+                    .is_none_or(|re| re.is_match(&format!("./{}", relative))),
+                None => false,
+            })
+        })
+        .filter(|file| !exclude.iter().any(|m| m.relative_match(file).is_some()))
+        .collect();
+    (matched, skipped)
 }
 
-fn handle_if_file(path: &Path) -> Option<PathBuf> {
-    if path.is_file() {
-        let canonical = normalize_path(path);
-        debug!("check_candidates: Found file directly {:?}", canonical);
-        return Some(canonical);
-    }
-    None
+/// A glob pattern split into its literal directory and the wildcard part below it
+struct GlobMatcher {
+    dir: PathBuf,
+    pattern: Pattern,
 }
 
-/// Checks various possibilities for the import path.
-/// Returns the resolved, canonicalized PathBuf if found.
-fn check_candidates(candidate: PathBuf, extensions: &[String]) -> Option<PathBuf> {
-    if let Some(canonical) = handle_if_file(&candidate) {
-        return Some(canonical);
-    }
-
-    for ext in extensions {
-        let file_name = candidate
-            .file_name()
-            .unwrap_or_default()
-            .to_string_lossy()
-            .into_owned();
+impl GlobMatcher {
+    fn new(base_dir: &Path, pattern: &str) -> Option<Self> {
+        let segments: Vec<&str> = pattern.split('/').collect();
+        let literal = segments
+            .iter()
+            .position(|s| s.contains(['*', '?', '[']))
+            .unwrap_or(segments.len().saturating_sub(1));
 
-        let new_file_name = format!("{}{}", file_name, ext);
-        let new_path = candidate.with_file_name(&new_file_name);
+        let dir = normalize_path(&base_dir.join(segments[..literal].join("/")));
+        let pattern = Pattern::new(&segments[literal..].join("/")).ok()?;
+        Some(Self { dir, pattern })
+    }
 
-        if let Some(canonical) = handle_if_file(&new_path) {
-            return Some(canonical);
-        }
+    /// Returns the file's path relative to the pattern directory if it matches
+    fn relative_match(&self, file: &Path) -> Option<String> {
+        let relative = file.strip_prefix(&self.dir).ok()?;
+        let relative = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..Default::default()
+        };
+        self.pattern
+            .matches_with(&relative, options)
+            .then_some(relative)
     }
+}
+
+/// Expands `{a,b}` alternatives, which the glob crate doesn't support.
+/// Alternatives may nest (`{a,{b,c}}`); unbalanced braces are left as they are.
+fn expand_braces(pattern: &str) -> Vec<String> {
+    let Some(open) = pattern.find('{') else {
+        return vec![pattern.to_string()];
+    };
 
-    // If candidate is a directory, try index files
-    if candidate.is_dir() {
-        for idx_ext in EXTENSIONS {
-            let idx_file = candidate.join(format!("index{}", idx_ext));
-            if let Some(canonical) = handle_if_file(&idx_file) {
-                return Some(canonical);
+    // Find the matching `}` and the commas that separate this level's alternatives
+    let mut depth = 0;
+    let mut close = None;
+    let mut separators = Vec::new();
+    for (i, c) in pattern.char_indices().skip_while(|&(i, _)| i < open) {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(i);
+                    break;
+                }
             }
+            ',' if depth == 1 => separators.push(i),
+            _ => {}
         }
     }
+    let Some(close) = close else {
+        return vec![pattern.to_string()];
+    };
 
-    debug!(
-        "check_candidates: Could not resolve candidate {:?}",
-        candidate
-    );
-    None
+    let mut start = open + 1;
+    let mut alternatives = Vec::new();
+    for end in separators.into_iter().chain([close]) {
+        alternatives.push(&pattern[start..end]);
+        start = end + 1;
+    }
+    alternatives
+        .into_iter()
+        .flat_map(|alt| {
+            expand_braces(&format!(
+                "{}{}{}",
+                &pattern[..open],
+                alt,
+                &pattern[close + 1..]
+            ))
+        })
+        .collect()
 }
 
 /// Finds all strongly connected components (cycles) in the dependency graph.
@@ -277,6 +667,11 @@ fn extract_cycle_info(
                     to_file: to_file.clone(),
                     line: edge_info.import.line,
                     import_text: edge_info.import.import_text.clone(),
+                    kind: edge_info.import.kind.clone(),
+                    specifiers: edge_info.import.specifiers.clone(),
+                    evaluation: edge_info.import.evaluation,
+                    is_speculative: edge_info.import.is_speculative,
+                    resolver: edge_info.resolver,
                 });
             }
         }
@@ -369,9 +764,14 @@ fn extract_self_loop_info(
                 to_file: file.clone(),
                 line: edge_info.import.line,
                 import_text: edge_info.import.import_text.clone(),
+                kind: edge_info.import.kind.clone(),
+                specifiers: edge_info.import.specifiers.clone(),
+                evaluation: edge_info.import.evaluation,
+                is_speculative: edge_info.import.is_speculative,
+                resolver: edge_info.resolver,
             };
 
-            let hash = compute_cycle_hash(&[cycle_edge.clone()], root);
+            let hash = compute_cycle_hash(std::slice::from_ref(&cycle_edge), root);
 
             return Some(CycleInfo {
                 edges: vec![cycle_edge],
@@ -416,11 +816,42 @@ fn deduplicate_cycles(cycles: Vec<CycleInfo>, root: &Path) -> Vec<CycleInfo> {
 
 /// Integrates cycle finding using Kosaraju's algorithm and deduplication.
 /// The `root` parameter is used to compute stable hashes with relative paths.
-pub fn get_unique_cycles(graph: &Graph<PathBuf, EdgeInfo>, root: &Path) -> Vec<CycleInfo> {
-    let cycles = find_all_cycles(graph, root);
+/// Edges excluded by `options` are ignored, so e.g. `eager_only` reports only
+/// cycles in which every import is read during module evaluation.
+pub fn get_unique_cycles(
+    graph: &Graph<PathBuf, EdgeInfo>,
+    root: &Path,
+    options: &CycleOptions,
+) -> Vec<CycleInfo> {
+    let filtered = graph.filter_map(
+        |_, node| Some(node.clone()),
+        |_, edge| options.includes_edge(edge).then(|| edge.clone()),
+    );
+    let cycles = find_all_cycles(&filtered, root);
     deduplicate_cycles(cycles, root)
 }
 
+/// Finds the cycles that only exist through test-framework mock edges.
+/// Returns nothing unless mocks are reported separately.
+pub fn get_mock_cycles(
+    graph: &Graph<PathBuf, EdgeInfo>,
+    root: &Path,
+    options: &CycleOptions,
+) -> Vec<CycleInfo> {
+    if options.mock_imports != MockImports::Separate {
+        return vec![];
+    }
+
+    let with_mocks = CycleOptions {
+        mock_imports: MockImports::Count,
+        ..options.clone()
+    };
+    get_unique_cycles(graph, root, &with_mocks)
+        .into_iter()
+        .filter(|cycle| cycle.edges.iter().any(|e| e.kind == ImportKind::Mock))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -435,12 +866,22 @@ mod tests {
                 to_file: PathBuf::from("/home/user/project/src/b.ts"),
                 line: 1,
                 import_text: "import { b } from './b'".to_string(),
+                kind: ImportKind::EsModule,
+                specifiers: vec![],
+                evaluation: Evaluation::Eager,
+                is_speculative: false,
+                resolver: "relative",
             },
             CycleEdge {
                 from_file: PathBuf::from("/home/user/project/src/b.ts"),
                 to_file: PathBuf::from("/home/user/project/src/a.ts"),
                 line: 1,
                 import_text: "import { a } from './a'".to_string(),
+                kind: ImportKind::EsModule,
+                specifiers: vec![],
+                evaluation: Evaluation::Eager,
+                is_speculative: false,
+                resolver: "relative",
             },
         ];
 
@@ -454,12 +895,22 @@ mod tests {
                 to_file: PathBuf::from("/different/path/project/src/b.ts"),
                 line: 1,
                 import_text: "import { b } from './b'".to_string(),
+                kind: ImportKind::EsModule,
+                specifiers: vec![],
+                evaluation: Evaluation::Eager,
+                is_speculative: false,
+                resolver: "relative",
             },
             CycleEdge {
                 from_file: PathBuf::from("/different/path/project/src/b.ts"),
                 to_file: PathBuf::from("/different/path/project/src/a.ts"),
                 line: 1,
                 import_text: "import { a } from './a'".to_string(),
+                kind: ImportKind::EsModule,
+                specifiers: vec![],
+                evaluation: Evaluation::Eager,
+                is_speculative: false,
+                resolver: "relative",
             },
         ];
 
@@ -482,12 +933,22 @@ mod tests {
                     to_file: PathBuf::from("/project/a.ts"),
                     line: 1,
                     import_text: String::new(),
+                    kind: ImportKind::EsModule,
+                    specifiers: vec![],
+                    evaluation: Evaluation::Eager,
+                    is_speculative: false,
+                    resolver: "relative",
                 },
                 CycleEdge {
                     from_file: PathBuf::from("/project/a.ts"),
                     to_file: PathBuf::from("/project/b.ts"),
                     line: 1,
                     import_text: String::new(),
+                    kind: ImportKind::EsModule,
+                    specifiers: vec![],
+                    evaluation: Evaluation::Eager,
+                    is_speculative: false,
+                    resolver: "relative",
                 },
             ],
             hash: String::new(),
@@ -501,4 +962,22 @@ mod tests {
             key
         );
     }
+
+    #[test]
+    fn test_expand_braces() {
+        assert_eq!(expand_braces("./pages/*.tsx"), vec!["./pages/*.tsx"]);
+        assert_eq!(
+            expand_braces("./{a,b}/*.{ts,tsx}"),
+            vec!["./a/*.ts", "./a/*.tsx", "./b/*.ts", "./b/*.tsx"]
+        );
+        assert_eq!(
+            expand_braces("./{a,{b,c}}/*.ts"),
+            vec!["./a/*.ts", "./b/*.ts", "./c/*.ts"]
+        );
+        assert_eq!(
+            expand_braces("./pages/{Home.tsx,{about,contact}.ts}"),
+            vec!["./pages/Home.tsx", "./pages/about.ts", "./pages/contact.ts"]
+        );
+        assert_eq!(expand_braces("./{a,b/*.ts"), vec!["./{a,b/*.ts"]);
+    }
 }
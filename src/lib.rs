@@ -0,0 +1,36 @@
+//! Circular dependency detection for JavaScript and TypeScript projects.
+//!
+//! The `cdd` binary is a thin wrapper around [`run`]. Tools that resolve some
+//! imports their own way can build the graph directly: implement [`Resolver`],
+//! push it onto [`ResolveOptions::resolvers`] and call [`build_dependency_graph`].
+
+mod aliases;
+mod app;
+mod cli;
+mod config;
+mod filesystem;
+mod graph;
+mod node_modules;
+mod output;
+mod parser;
+mod resolver;
+mod sfc;
+mod tsconfig;
+mod usage;
+mod utils;
+#[cfg(feature = "watch")]
+mod watch;
+mod workspace;
+
+pub use aliases::AliasMap;
+pub use app::run;
+pub use graph::{
+    build_dependency_graph, BoundaryEdge, DependencyGraph, EdgeInfo, ModuleSuffixes,
+    PlatformSuffixes, ResolveOptions, UnresolvedImport, UnresolvedKind,
+};
+pub use parser::{
+    Evaluation, GlobImport, ImportInfo, ImportKind, ImportedBinding, ParserOptions, SpecifierKind,
+};
+pub use resolver::{ResolveContext, ResolveRequest, Resolver};
+pub use tsconfig::{PathAliases, TsConfigCache};
+pub use workspace::{ExportValue, Exports, PackageInfo, Workspace, WorkspaceFormat};
@@ -0,0 +1,214 @@
+//! Node's `node_modules` lookup for bare specifiers.
+//!
+//! pnpm and Yarn's `node-modules` linker often wire local packages together with
+//! symlinks in `node_modules` rather than a `workspaces` field. Following those
+//! links lands on the package's real directory, so a linked package resolves to
+//! its source files like a workspace package does. Anything whose real path is
+//! still inside a `node_modules` directory is a third-party package.
+
+use crate::utils::split_package_name;
+use crate::workspace::PackageInfo;
+use log::debug;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// What a bare specifier resolved to through `node_modules`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeModulesTarget {
+    /// A file of a locally linked package (the symlink target is outside `node_modules`)
+    Local(PathBuf),
+    /// A third-party package, identified by its name and real directory
+    External { name: String, path: PathBuf },
+}
+
+/// Resolves bare specifiers by walking up from the importer through each
+/// `node_modules` directory, like Node does. Packages are cached by their real
+/// directory, so each package.json is read once.
+#[derive(Debug, Default)]
+pub struct NodeModules {
+    /// Real package directory -> package info
+    packages: Mutex<HashMap<PathBuf, Arc<PackageInfo>>>,
+}
+
+impl NodeModules {
+    /// Resolves `specifier` imported from `importer`. `conditions` select among
+    /// conditional exports of linked packages. Returns None for relative, `#` and
+    /// protocol (`node:`) specifiers, and for packages that aren't installed.
+    pub fn resolve(
+        &self,
+        importer: &Path,
+        specifier: &str,
+        conditions: &[String],
+    ) -> Option<NodeModulesTarget> {
+        if !is_bare_specifier(specifier) {
+            return None;
+        }
+        let (name, subpath) = split_package_name(specifier);
+
+        for dir in importer.parent()?.ancestors() {
+            // Node never looks for `node_modules/node_modules`
+            if dir.file_name().is_some_and(|n| n == "node_modules") {
+                continue;
+            }
+            let candidate = dir.join("node_modules").join(name);
+            // A broken symlink isn't a directory either, so the walk goes on, like in Node
+            if !candidate.is_dir() {
+                continue;
+            }
+
+            // Follows symlinks, so linked packages land on their real directory
+            let real = candidate.canonicalize().ok()?;
+            if is_third_party(&real) {
+                debug!("Resolved '{}' to external package at {:?}", specifier, real);
+                return Some(NodeModulesTarget::External {
+                    name: name.to_string(),
+                    path: real,
+                });
+            }
+
+            let info = self.package(&real, name);
+            let resolved = if subpath.is_empty() {
+                info.resolve_entry(conditions)
+            } else {
+                info.resolve_subpath(subpath, conditions)
+            };
+            debug!(
+                "Resolved '{}' through linked package {:?} to {:?}",
+                specifier, candidate, resolved
+            );
+            return resolved.map(NodeModulesTarget::Local);
+        }
+
+        None
+    }
+
+    /// Loads a linked package, registering it under `name` if its package.json
+    /// doesn't have one.
+    fn package(&self, dir: &Path, name: &str) -> Arc<PackageInfo> {
+        let mut packages = self.packages.lock().unwrap_or_else(|e| e.into_inner());
+        packages
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| {
+                Arc::new(
+                    PackageInfo::load(&dir.join("package.json")).unwrap_or_else(|| PackageInfo {
+                        name: name.to_string(),
+                        path: dir.to_path_buf(),
+                        main: None,
+                        module: None,
+                        exports: None,
+                        tsconfig: None,
+                    }),
+                )
+            })
+            .clone()
+    }
+}
+
+/// Whether a specifier names a package, rather than a path, a `#` import or a
+/// protocol such as `node:fs` or `virtual:module`.
+fn is_bare_specifier(specifier: &str) -> bool {
+    !specifier.is_empty()
+        && !specifier.starts_with(['.', '/', '#'])
+        && !specifier.contains(':')
+        && !Path::new(specifier).is_absolute()
+}
+
+fn is_third_party(path: &Path) -> bool {
+    path.components().any(|c| c.as_os_str() == "node_modules")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn conditions() -> Vec<String> {
+        vec!["import".to_string()]
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_symlinked_package_resolves_to_source() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        let ui = root.join("packages/ui");
+        fs::create_dir_all(ui.join("src")).unwrap();
+        fs::write(
+            ui.join("package.json"),
+            r#"{"name": "@acme/ui", "exports": {".": "./src/index.ts", "./button": "./src/button.ts"}}"#,
+        )
+        .unwrap();
+        fs::write(ui.join("src/index.ts"), "").unwrap();
+        fs::write(ui.join("src/button.ts"), "").unwrap();
+
+        let app = root.join("apps/web");
+        fs::create_dir_all(app.join("node_modules/@acme")).unwrap();
+        std::os::unix::fs::symlink("../../../../packages/ui", app.join("node_modules/@acme/ui"))
+            .unwrap();
+        let importer = app.join("src/main.ts");
+
+        let node_modules = NodeModules::default();
+        assert_eq!(
+            node_modules.resolve(&importer, "@acme/ui", &conditions()),
+            Some(NodeModulesTarget::Local(ui.join("src/index.ts")))
+        );
+        assert_eq!(
+            node_modules.resolve(&importer, "@acme/ui/button", &conditions()),
+            Some(NodeModulesTarget::Local(ui.join("src/button.ts")))
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_broken_symlink_falls_back_to_parent_node_modules() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        let lodash = root.join("node_modules/lodash");
+        fs::create_dir_all(&lodash).unwrap();
+        let app = root.join("apps/web");
+        fs::create_dir_all(app.join("node_modules")).unwrap();
+        std::os::unix::fs::symlink("../missing/lodash", app.join("node_modules/lodash")).unwrap();
+
+        let node_modules = NodeModules::default();
+        assert_eq!(
+            node_modules.resolve(&app.join("src/main.ts"), "lodash", &conditions()),
+            Some(NodeModulesTarget::External {
+                name: "lodash".to_string(),
+                path: lodash,
+            })
+        );
+    }
+
+    #[test]
+    fn test_third_party_package_in_parent_node_modules() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        let lodash = root.join("node_modules/lodash");
+        fs::create_dir_all(&lodash).unwrap();
+        fs::write(lodash.join("package.json"), r#"{"name": "lodash"}"#).unwrap();
+        let importer = root.join("src/deep/file.ts");
+
+        let node_modules = NodeModules::default();
+        assert_eq!(
+            node_modules.resolve(&importer, "lodash/get", &conditions()),
+            Some(NodeModulesTarget::External {
+                name: "lodash".to_string(),
+                path: lodash,
+            })
+        );
+        assert_eq!(
+            node_modules.resolve(&importer, "react", &conditions()),
+            None
+        );
+        assert_eq!(
+            node_modules.resolve(&importer, "node:fs", &conditions()),
+            None
+        );
+        assert_eq!(
+            node_modules.resolve(&importer, "./lodash", &conditions()),
+            None
+        );
+    }
+}
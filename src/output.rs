@@ -6,8 +6,10 @@
 //! - Hash computation for CI validation
 
 use crate::config::AllowedCycle;
-use crate::graph::CycleInfo;
-use crate::utils::{hash_strings, relative_path_string};
+use crate::graph::{BoundaryEdge, CycleInfo, UnresolvedImport, UnresolvedKind};
+use crate::parser::{Evaluation, ImportKind, ImportedBinding};
+use crate::utils::{hash_strings, path_from_root, relative_path_string};
+use crate::workspace::WorkspaceFormat;
 use colored::*;
 use serde::Serialize;
 use std::collections::HashSet;
@@ -33,6 +35,21 @@ pub struct JsonOutput {
     pub cycles_hash: String,
     /// Detailed information about each cycle
     pub cycles: Vec<JsonCycle>,
+    /// Cycles that only exist through test-framework mocks (not counted)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub mock_cycles: Vec<JsonCycle>,
+    /// Imports that resolve outside the scanned directory (not followed)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub boundary_edges: Vec<JsonBoundaryEdge>,
+    /// Third-party packages recorded as external nodes (with `--external-nodes`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub external_packages: Vec<String>,
+    /// Imports that should point at a project file but don't resolve
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unresolved_imports: Vec<JsonUnresolvedImport>,
+    /// The monorepo tool the workspace packages were found with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_format: Option<WorkspaceFormat>,
 }
 
 /// JSON representation of a single cycle
@@ -55,6 +72,46 @@ pub struct JsonEdge {
     pub line: u32,
     /// The import statement text
     pub import_text: String,
+    /// The kind of import
+    pub kind: ImportKind,
+    /// The bindings that cross this edge
+    pub specifiers: Vec<ImportedBinding>,
+    /// Whether the bindings are read during module evaluation
+    pub evaluation: Evaluation,
+    /// Whether the edge was guessed from a computed import specifier
+    pub is_speculative: bool,
+    /// The resolver that produced this edge
+    pub resolver: &'static str,
+}
+
+/// JSON representation of an import leaving the scanned directory
+#[derive(Debug, Serialize)]
+pub struct JsonBoundaryEdge {
+    /// Source file (relative path)
+    pub from_file: String,
+    /// Target file, relative to the scanned directory (starts with `..`)
+    pub to_file: String,
+    /// Line number of the import
+    pub line: u32,
+    /// The import statement text
+    pub import_text: String,
+    /// The resolver that produced this edge
+    pub resolver: &'static str,
+}
+
+/// JSON representation of an import that doesn't resolve
+#[derive(Debug, Serialize)]
+pub struct JsonUnresolvedImport {
+    /// Source file (relative path)
+    pub from_file: String,
+    /// Line number of the import
+    pub line: u32,
+    /// The import specifier that didn't resolve
+    pub specifier: String,
+    /// The import statement text
+    pub import_text: String,
+    /// Why the import should have resolved
+    pub kind: UnresolvedKind,
 }
 
 /// JSON error output structure
@@ -83,6 +140,7 @@ pub fn compute_cycles_hash(cycles: &[CycleInfo]) -> String {
 /// 1) Circular dependency [hash]:
 ///    src/a.ts:3
 ///    | import { b } from './b';
+///    | uses: b
 ///    v
 ///    src/b.ts:2
 ///    | import { a } from './a';
@@ -101,44 +159,185 @@ pub fn print_cycles_detailed(cycles: &[CycleInfo], root: &Path) {
     );
 
     for (i, cycle) in cycles.iter().enumerate() {
+        print_cycle(i, cycle, root);
+    }
+}
+
+/// Print cycles that only exist through test-framework mocks. These are
+/// informational and don't count towards the expected cycles or hash.
+pub fn print_mock_cycles(cycles: &[CycleInfo], root: &Path) {
+    if cycles.is_empty() {
+        return;
+    }
+
+    log::info!(
+        "{} Found {} circular dependencies through test mocks (not counted):\n",
+        "!".yellow().bold(),
+        cycles.len().to_string().yellow()
+    );
+
+    for (i, cycle) in cycles.iter().enumerate() {
+        print_cycle(i, cycle, root);
+    }
+}
+
+/// Print imports that resolve outside the scanned directory. They aren't
+/// followed, so cycles through them can't be detected from this directory.
+pub fn print_boundary_edges(edges: &[BoundaryEdge], root: &Path) {
+    if edges.is_empty() {
+        return;
+    }
+
+    log::info!(
+        "{} {} imports point outside the scanned directory (not followed):",
+        "i".cyan().bold(),
+        edges.len().to_string().cyan()
+    );
+    for edge in edges {
+        log::info!(
+            "   {}:{} -> {}",
+            relative_path_string(&edge.from_file, root),
+            edge.line,
+            path_from_root(&edge.to_file, root).dimmed()
+        );
+    }
+    log::info!("");
+}
+
+/// Print imports that should point at a project file but don't resolve. A
+/// missing edge can hide a cycle, so these are worth fixing first.
+pub fn print_unresolved_imports(imports: &[UnresolvedImport], root: &Path) {
+    if imports.is_empty() {
+        return;
+    }
+
+    log::info!(
+        "{} {} imports don't resolve to a file:",
+        "!".yellow().bold(),
+        imports.len().to_string().yellow()
+    );
+    for import in imports {
+        let kind = match import.kind {
+            UnresolvedKind::Relative => "relative",
+            UnresolvedKind::Alias => "alias",
+            UnresolvedKind::Package => "workspace package",
+            UnresolvedKind::Glob => "glob pattern",
+        };
+        log::info!(
+            "   {}:{} '{}' {}",
+            relative_path_string(&import.from_file, root).cyan(),
+            import.line.to_string().yellow(),
+            import.specifier,
+            format!("({})", kind).dimmed()
+        );
+    }
+    log::info!("");
+}
+
+fn print_cycle(i: usize, cycle: &CycleInfo, root: &Path) {
+    log::info!(
+        "{}) Circular dependency [{}]:",
+        (i + 1).to_string().bright_blue().bold(),
+        cycle.hash.dimmed()
+    );
+
+    for (j, edge) in cycle.edges.iter().enumerate() {
+        let from_relative = relative_path_string(&edge.from_file, root);
+
+        // Print the file and line number, marking imports only read after evaluation
+        // and edges guessed from computed specifiers
+        let deferred = if edge.evaluation == Evaluation::Deferred {
+            " (deferred)".dimmed().to_string()
+        } else {
+            String::new()
+        };
+        let speculative = if edge.is_speculative {
+            " (speculative)".yellow().to_string()
+        } else {
+            String::new()
+        };
         log::info!(
-            "{}) Circular dependency [{}]:",
-            (i + 1).to_string().bright_blue().bold(),
-            cycle.hash.dimmed()
+            "   {}:{}{}{}",
+            from_relative.cyan(),
+            edge.line.to_string().yellow(),
+            deferred,
+            speculative
         );
 
-        for (j, edge) in cycle.edges.iter().enumerate() {
-            let from_relative = relative_path_string(&edge.from_file, root);
-
-            // Print the file and line number
-            log::info!(
-                "   {}:{}",
-                from_relative.cyan(),
-                edge.line.to_string().yellow()
-            );
-
-            // Print the import statement
-            let import_text = edge.import_text.trim();
-            log::info!("   {} {}", "|".dimmed(), import_text.dimmed());
-
-            // Print arrow or cycle indicator
-            if j < cycle.edges.len() - 1 {
-                log::info!("   {}", "v".bright_blue());
-            } else {
-                log::info!("   {} (cycle)", "^--".bright_blue());
-            }
+        // Print the import statement
+        let import_text = edge.import_text.trim();
+        log::info!("   {} {}", "|".dimmed(), import_text.dimmed());
+
+        // Print the bindings that cross this edge
+        if !edge.specifiers.is_empty() {
+            let bindings = edge
+                .specifiers
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            log::info!("   {} uses: {}", "|".dimmed(), bindings.bright_white());
+        }
+
+        // Print arrow or cycle indicator
+        if j < cycle.edges.len() - 1 {
+            log::info!("   {}", "v".bright_blue());
+        } else {
+            log::info!("   {} (cycle)", "^--".bright_blue());
         }
-        log::info!("");
     }
+    log::info!("");
 }
 
 /// Generate JSON output structure for cycles.
 ///
 /// All file paths in the output are relative to the root directory.
-pub fn generate_json_output(cycles: &[CycleInfo], root: &Path, total_files: usize) -> JsonOutput {
+/// `mock_cycles`, `boundary_edges`, `external_packages` and `unresolved_imports`
+/// are listed separately and don't affect the totals or hash.
+pub fn generate_json_output(
+    cycles: &[CycleInfo],
+    mock_cycles: &[CycleInfo],
+    boundary_edges: &[BoundaryEdge],
+    external_packages: &[String],
+    unresolved_imports: &[UnresolvedImport],
+    root: &Path,
+    total_files: usize,
+) -> JsonOutput {
     let cycles_hash = compute_cycles_hash(cycles);
 
-    let json_cycles: Vec<JsonCycle> = cycles
+    JsonOutput {
+        total_files,
+        total_cycles: cycles.len(),
+        cycles_hash,
+        cycles: json_cycles(cycles, root),
+        mock_cycles: json_cycles(mock_cycles, root),
+        boundary_edges: boundary_edges
+            .iter()
+            .map(|edge| JsonBoundaryEdge {
+                from_file: relative_path_string(&edge.from_file, root),
+                to_file: path_from_root(&edge.to_file, root),
+                line: edge.line,
+                import_text: edge.import_text.clone(),
+                resolver: edge.resolver,
+            })
+            .collect(),
+        external_packages: external_packages.to_vec(),
+        unresolved_imports: unresolved_imports
+            .iter()
+            .map(|import| JsonUnresolvedImport {
+                from_file: relative_path_string(&import.from_file, root),
+                line: import.line,
+                specifier: import.specifier.clone(),
+                import_text: import.import_text.clone(),
+                kind: import.kind,
+            })
+            .collect(),
+        workspace_format: None,
+    }
+}
+
+fn json_cycles(cycles: &[CycleInfo], root: &Path) -> Vec<JsonCycle> {
+    cycles
         .iter()
         .map(|cycle| {
             let edges: Vec<JsonEdge> = cycle
@@ -149,6 +348,11 @@ pub fn generate_json_output(cycles: &[CycleInfo], root: &Path, total_files: usiz
                     to_file: relative_path_string(&edge.to_file, root),
                     line: edge.line,
                     import_text: edge.import_text.clone(),
+                    kind: edge.kind.clone(),
+                    specifiers: edge.specifiers.clone(),
+                    evaluation: edge.evaluation,
+                    is_speculative: edge.is_speculative,
+                    resolver: edge.resolver,
                 })
                 .collect();
 
@@ -157,14 +361,7 @@ pub fn generate_json_output(cycles: &[CycleInfo], root: &Path, total_files: usiz
                 edges,
             }
         })
-        .collect();
-
-    JsonOutput {
-        total_files,
-        total_cycles: cycles.len(),
-        cycles_hash,
-        cycles: json_cycles,
-    }
+        .collect()
 }
 
 /// Print JSON output to stdout.
@@ -286,6 +483,11 @@ mod tests {
                 to_file: root.join(pair[1]),
                 line: 1,
                 import_text: format!("import from '{}'", pair[1]),
+                kind: ImportKind::EsModule,
+                specifiers: vec![],
+                evaluation: Evaluation::Eager,
+                is_speculative: false,
+                resolver: "relative",
             })
             .take(files.len())
             .collect();
@@ -351,7 +553,7 @@ src/c.ts > src/d.ts > src/e.ts
         let root = PathBuf::from("/project");
         let cycles = vec![make_cycle(&["a.ts", "b.ts"], &root)];
 
-        let output = generate_json_output(&cycles, &root, 10);
+        let output = generate_json_output(&cycles, &[], &[], &[], &[], &root, 10);
 
         assert_eq!(output.total_files, 10);
         assert_eq!(output.total_cycles, 1);
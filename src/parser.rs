@@ -1,8 +1,16 @@
-use log::warn;
+use crate::sfc::{extract_script_blocks, ScriptBlock, SfcKind};
+use crate::usage::{analyze_usage, ModuleUsage};
+use crate::utils::is_declaration_file;
+use glob::Pattern;
+use log::{debug, warn};
+use regex::Regex;
 use serde::Serialize;
+use std::collections::HashMap;
 use std::path::Path;
+use std::sync::OnceLock;
+use swc_common::comments::{CommentKind, SingleThreadedComments};
 use swc_common::sync::Lrc;
-use swc_common::{SourceFile, SourceMap, Span};
+use swc_common::{BytePos, FileName, SourceFile, SourceMap, Span, Spanned};
 use swc_ecma_ast::{EsVersion, *};
 use swc_ecma_parser::{lexer::Lexer, EsSyntax, Parser, StringInput, Syntax, TsSyntax};
 use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
@@ -18,6 +26,16 @@ pub enum ImportKind {
     Dynamic,
     /// Re-export: `export * from './foo'` or `export { x } from './foo'`
     ReExport,
+    /// TypeScript import-equals: `import foo = require('./foo')`
+    ImportEquals,
+    /// Triple-slash directive: `/// <reference path="./types.d.ts" />`
+    TripleSlash,
+    /// Bundler glob import: `import.meta.glob('./pages/*.tsx')` or `require.context('./modules')`
+    Glob,
+    /// Test-framework module mock: `jest.mock('./db')`, `vi.mock('../api')`, `jest.requireActual('./x')`
+    Mock,
+    /// Worker script: `new Worker(new URL('./worker.ts', import.meta.url))`
+    Worker,
 }
 
 impl std::fmt::Display for ImportKind {
@@ -27,10 +45,86 @@ impl std::fmt::Display for ImportKind {
             ImportKind::CommonJs => write!(f, "require"),
             ImportKind::Dynamic => write!(f, "dynamic import"),
             ImportKind::ReExport => write!(f, "re-export"),
+            ImportKind::ImportEquals => write!(f, "import require"),
+            ImportKind::TripleSlash => write!(f, "reference"),
+            ImportKind::Glob => write!(f, "glob import"),
+            ImportKind::Mock => write!(f, "mock"),
+            ImportKind::Worker => write!(f, "worker"),
         }
     }
 }
 
+/// When an import's bindings are read, relative to the importing module's evaluation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Evaluation {
+    /// A binding is read while the module is evaluated (`class A extends B`, top-level calls).
+    /// In a cycle, this can observe an uninitialized binding at runtime.
+    Eager,
+    /// Bindings are only read later, e.g. inside function bodies or after an async import
+    Deferred,
+}
+
+/// How a binding is imported from the target module
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SpecifierKind {
+    /// Named import: `import { x } from './foo'`
+    Named,
+    /// Default import: `import x from './foo'`
+    Default,
+    /// Namespace import: `import * as ns from './foo'` or `export * from './foo'`
+    Namespace,
+}
+
+/// A single binding that crosses an import edge
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImportedBinding {
+    /// How the binding is imported
+    pub kind: SpecifierKind,
+    /// The name exported by the target module (`default` for default imports, `*` for namespaces)
+    pub imported: String,
+    /// The local (or re-exported) name in the importing module
+    pub local: String,
+    /// Whether this binding is type-only (`import { type Foo }`)
+    pub is_type_only: bool,
+}
+
+impl ImportedBinding {
+    fn new(kind: SpecifierKind, imported: String, local: String, is_type_only: bool) -> Self {
+        Self {
+            kind,
+            imported,
+            local,
+            is_type_only,
+        }
+    }
+}
+
+impl std::fmt::Display for ImportedBinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_type_only {
+            write!(f, "type ")?;
+        }
+        if self.imported == self.local {
+            write!(f, "{}", self.imported)
+        } else {
+            write!(f, "{} as {}", self.imported, self.local)
+        }
+    }
+}
+
+/// The file patterns of a bundler glob import, expanded against the scanned files
+/// when the dependency graph is built
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GlobImport {
+    /// Glob patterns relative to the importing file; a leading `!` excludes matches
+    pub patterns: Vec<String>,
+    /// Regex a match must satisfy, tested against its path relative to the pattern's
+    /// directory as `./dir/file.js` (the `require.context` filter)
+    pub filter: Option<String>,
+    /// Whether the matched modules are loaded as soon as the call runs
+    pub eager: bool,
+}
+
 /// Information about a single import statement
 #[derive(Debug, Clone, Serialize)]
 pub struct ImportInfo {
@@ -44,6 +138,19 @@ pub struct ImportInfo {
     pub is_type_only: bool,
     /// The kind of import
     pub kind: ImportKind,
+    /// The bindings imported or re-exported by this statement
+    pub specifiers: Vec<ImportedBinding>,
+    /// Whether the bindings are read during module evaluation
+    pub evaluation: Evaluation,
+    /// Whether this value import is only used as a type, so TypeScript erases it
+    /// when compiling (without `verbatimModuleSyntax`)
+    pub is_elided: bool,
+    /// The patterns to expand, for [`ImportKind::Glob`] imports and computed specifiers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub glob: Option<GlobImport>,
+    /// Whether the target was guessed from a computed specifier (`import(`./${x}.ts`)`),
+    /// so the edge may not exist at runtime
+    pub is_speculative: bool,
 }
 
 /// Options for import extraction
@@ -51,6 +158,9 @@ pub struct ImportInfo {
 pub struct ParserOptions {
     /// If true, type-only imports (import type { Foo }) are excluded
     pub ignore_type_imports: bool,
+    /// If true, value imports whose bindings are only used as types are excluded too,
+    /// matching what TypeScript emits without `verbatimModuleSyntax`
+    pub ignore_elided_imports: bool,
 }
 
 /// Extracts import paths from a JavaScript/TypeScript file.
@@ -60,29 +170,113 @@ pub struct ParserOptions {
 /// - Re-exports (`export * from './foo'`)
 /// - CommonJS requires (`require('./foo')`)
 /// - Dynamic imports (`import('./foo')`)
+/// - TypeScript import-equals (`import foo = require('./foo')`)
+/// - Triple-slash references (`/// <reference path="./types.d.ts" />`)
+///
+/// Vue, Svelte and Astro components are supported by parsing their script blocks.
 ///
 /// Returns a Vec of ImportInfo with line numbers and import text.
 pub fn get_imports_from_file(path: &Path, options: &ParserOptions) -> Vec<ImportInfo> {
-    let (module, source_map, source_file) = match parse_file_to_ast(path) {
-        Some(m) => m,
-        None => return vec![],
+    if let Some(kind) = SfcKind::from_path(path) {
+        return get_imports_from_component(path, kind, options);
+    }
+
+    let source = match std::fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(_) => return vec![],
+    };
+
+    let mut imports = collect_imports(path, source, get_syntax_for_file(path), 0, true, options);
+
+    // Declaration files have no runtime code, so every edge they create is type-only
+    if is_declaration_file(path) {
+        if options.ignore_type_imports {
+            return vec![];
+        }
+        for import in &mut imports {
+            import.is_type_only = true;
+        }
+    }
+
+    imports
+}
+
+/// Extracts imports from every script block of a single-file component.
+fn get_imports_from_component(
+    path: &Path,
+    kind: SfcKind,
+    options: &ParserOptions,
+) -> Vec<ImportInfo> {
+    let source = match std::fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(_) => return vec![],
     };
 
-    let mut collector = ImportCollector::new(options.ignore_type_imports, source_map, source_file);
+    extract_script_blocks(&source, kind)
+        .into_iter()
+        .flat_map(|block| {
+            let syntax = get_syntax_for_block(&block);
+            // Templates can reference script bindings, so usage can't prove an import unused
+            collect_imports(
+                path,
+                block.content,
+                syntax,
+                block.line_offset,
+                false,
+                options,
+            )
+        })
+        .collect()
+}
+
+/// Parses source code and collects its imports.
+/// `line_offset` is added to every reported line (used for embedded script blocks).
+/// `detect_elision` enables marking TypeScript imports that are only used as types.
+fn collect_imports(
+    path: &Path,
+    source: String,
+    syntax: Syntax,
+    line_offset: u32,
+    detect_elision: bool,
+    options: &ParserOptions,
+) -> Vec<ImportInfo> {
+    let detect_elision = detect_elision && matches!(syntax, Syntax::Typescript(_));
+    let comments = SingleThreadedComments::default();
+    let (module, source_map, source_file) =
+        match parse_source_to_ast(path, source, syntax, &comments) {
+            Some(m) => m,
+            None => return vec![],
+        };
+
+    let mut collector = ImportCollector::new(
+        options.ignore_type_imports,
+        source_map,
+        source_file,
+        line_offset,
+    );
+    collector.collect_references(&module, &comments);
     module.visit_with(&mut collector);
+    collector.classify(&analyze_usage(&module), detect_elision);
+
+    if options.ignore_elided_imports {
+        collector.imports.retain(|import| !import.is_elided);
+    }
     collector.imports
 }
 
 fn get_syntax_for_file(path: &Path) -> Syntax {
-    let ext = path
-        .extension()
-        .and_then(|e| e.to_str())
-        .unwrap_or("")
-        .to_lowercase();
+    let ext = if is_declaration_file(path) {
+        "dts".to_string()
+    } else {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_lowercase()
+    };
 
     match ext.as_str() {
-        // TypeScript files
-        "ts" => Syntax::Typescript(TsSyntax {
+        // TypeScript files (including ESM/CJS variants)
+        "ts" | "mts" | "cts" => Syntax::Typescript(TsSyntax {
             tsx: false,
             decorators: true,
             dts: false,
@@ -126,12 +320,47 @@ fn get_syntax_for_file(path: &Path) -> Syntax {
     }
 }
 
-fn parse_file_to_ast(path: &Path) -> Option<(Module, Lrc<SourceMap>, Lrc<SourceFile>)> {
+fn get_syntax_for_block(block: &ScriptBlock) -> Syntax {
+    if block.is_typescript() {
+        Syntax::Typescript(TsSyntax {
+            tsx: block.is_jsx(),
+            decorators: true,
+            dts: false,
+            no_early_errors: false,
+            ..Default::default()
+        })
+    } else {
+        Syntax::Es(EsSyntax {
+            jsx: block.is_jsx(),
+            decorators: true,
+            ..Default::default()
+        })
+    }
+}
+
+/// Parses a standalone script, such as a bundler config, into a module.
+pub fn parse_file(path: &Path) -> Option<Module> {
+    let source = std::fs::read_to_string(path).ok()?;
+    let comments = SingleThreadedComments::default();
+    parse_source_to_ast(path, source, get_syntax_for_file(path), &comments)
+        .map(|(module, _, _)| module)
+}
+
+fn parse_source_to_ast(
+    path: &Path,
+    source: String,
+    syntax: Syntax,
+    comments: &SingleThreadedComments,
+) -> Option<(Module, Lrc<SourceMap>, Lrc<SourceFile>)> {
     let cm = Lrc::new(SourceMap::default());
-    let fm = cm.load_file(path).ok()?;
+    let fm = cm.new_source_file(Lrc::new(FileName::Real(path.to_path_buf())), source);
 
-    let syntax = get_syntax_for_file(path);
-    let lexer = Lexer::new(syntax, EsVersion::Es2022, StringInput::from(&*fm), None);
+    let lexer = Lexer::new(
+        syntax,
+        EsVersion::Es2022,
+        StringInput::from(&*fm),
+        Some(comments),
+    );
     let mut parser = Parser::new_from(lexer);
 
     match parser.parse_module() {
@@ -150,11 +379,25 @@ fn parse_file_to_ast(path: &Path) -> Option<(Module, Lrc<SourceMap>, Lrc<SourceF
     }
 }
 
+fn reference_path_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#"^/\s*<reference\s+path\s*=\s*["']([^"']+)["']"#)
+            .expect("valid reference regex")
+    })
+}
+
 struct ImportCollector {
     imports: Vec<ImportInfo>,
+    /// Span of each collected import, parallel to `imports`
+    spans: Vec<Span>,
     ignore_type_imports: bool,
     source_map: Lrc<SourceMap>,
     source_file: Lrc<SourceFile>,
+    /// Lines preceding the parsed source in the original file
+    line_offset: u32,
+    /// Bindings for `require()`/`import()` calls assigned to a variable, keyed by call position
+    call_bindings: HashMap<BytePos, Vec<ImportedBinding>>,
 }
 
 impl ImportCollector {
@@ -162,12 +405,16 @@ impl ImportCollector {
         ignore_type_imports: bool,
         source_map: Lrc<SourceMap>,
         source_file: Lrc<SourceFile>,
+        line_offset: u32,
     ) -> Self {
         Self {
             imports: vec![],
+            spans: vec![],
             ignore_type_imports,
             source_map,
             source_file,
+            line_offset,
+            call_bindings: HashMap::new(),
         }
     }
 
@@ -197,7 +444,7 @@ impl ImportCollector {
     /// Extract line number from a span
     fn get_line(&self, span: Span) -> u32 {
         let loc = self.source_map.lookup_char_pos(span.lo);
-        loc.line as u32
+        loc.line as u32 + self.line_offset
     }
 
     /// Extract the source text for a span
@@ -213,7 +460,68 @@ impl ImportCollector {
         }
     }
 
-    fn add_import(&mut self, source: String, span: Span, is_type_only: bool, kind: ImportKind) {
+    /// Collect triple-slash `/// <reference path="..." />` directives.
+    ///
+    /// TypeScript only honours these before the first statement, and they never
+    /// survive compilation, so they are recorded as type-only.
+    fn collect_references(&mut self, module: &Module, comments: &SingleThreadedComments) {
+        if self.ignore_type_imports {
+            return;
+        }
+
+        let first_stmt = module
+            .body
+            .first()
+            .map_or(BytePos(u32::MAX), |item| item.span().lo);
+
+        let (leading, trailing) = comments.borrow_all();
+        let mut directives: Vec<_> = leading
+            .values()
+            .chain(trailing.values())
+            .flatten()
+            .filter(|c| c.kind == CommentKind::Line && c.span.lo < first_stmt)
+            .cloned()
+            .collect();
+        directives.sort_by_key(|c| c.span.lo);
+
+        for comment in directives {
+            if let Some(caps) = reference_path_regex().captures(&comment.text) {
+                // `path` is always relative to the file, even without a leading `./`
+                let path = &caps[1];
+                let source = if path.starts_with('.') || Path::new(path).is_absolute() {
+                    path.to_string()
+                } else {
+                    format!("./{}", path)
+                };
+                self.add_import(source, comment.span, true, ImportKind::TripleSlash, vec![]);
+            }
+        }
+    }
+
+    fn add_import(
+        &mut self,
+        source: String,
+        span: Span,
+        is_type_only: bool,
+        kind: ImportKind,
+        specifiers: Vec<ImportedBinding>,
+    ) {
+        let kind = match kind {
+            ImportKind::EsModule | ImportKind::ReExport | ImportKind::Dynamic => {
+                match query_kind(&source) {
+                    QueryKind::Worker => ImportKind::Worker,
+                    QueryKind::Asset => {
+                        debug!(
+                            "Skipped import that doesn't evaluate the module: '{}'",
+                            source
+                        );
+                        return;
+                    }
+                    QueryKind::Module => kind,
+                }
+            }
+            kind => kind,
+        };
         let line = self.get_line(span);
         let import_text = self.get_span_text(span);
 
@@ -223,8 +531,577 @@ impl ImportCollector {
             import_text,
             is_type_only,
             kind,
+            specifiers,
+            evaluation: Evaluation::Eager,
+            is_elided: false,
+            glob: None,
+            is_speculative: false,
         });
+        self.spans.push(span);
+    }
+
+    /// Add a `require()` or `import()` call, expanding computed specifiers as a glob
+    fn add_call_import(&mut self, call: &CallExpr, kind: ImportKind) {
+        let Some(arg) = call.args.first() else {
+            return;
+        };
+        let Some(specifier) = call_specifier(&arg.expr) else {
+            return;
+        };
+
+        let bindings = self.call_bindings.remove(&call.span.lo);
+        match specifier {
+            CallSpecifier::Static(source) => {
+                self.add_import(source, call.span, false, kind, bindings.unwrap_or_default());
+            }
+            CallSpecifier::Pattern(pattern) => {
+                let eager = kind == ImportKind::CommonJs;
+                self.add_import(
+                    pattern.clone(),
+                    call.span,
+                    false,
+                    kind,
+                    bindings.unwrap_or_default(),
+                );
+                if let Some(import) = self.imports.last_mut() {
+                    import.glob = Some(GlobImport {
+                        patterns: vec![pattern],
+                        filter: None,
+                        eager,
+                    });
+                    import.is_speculative = true;
+                }
+            }
+        }
+    }
+
+    fn add_glob_import(&mut self, span: Span, glob: GlobImport) {
+        let source = glob.patterns.first().cloned().unwrap_or_default();
+        self.add_import(source, span, false, ImportKind::Glob, vec![]);
+        if let Some(import) = self.imports.last_mut() {
+            import.glob = Some(glob);
+        }
+    }
+
+    /// Mark each collected import as eager or deferred based on how the module uses it,
+    /// and (for TypeScript) whether it would be elided.
+    fn classify(&mut self, usage: &ModuleUsage, detect_elision: bool) {
+        for (import, span) in self.imports.iter_mut().zip(&self.spans) {
+            import.is_elided = detect_elision && is_elided(import, usage);
+            import.evaluation = evaluation_for(import, *span, usage);
+        }
+    }
+}
+
+/// Whether TypeScript would elide a value import because none of its bindings
+/// appears in a value position. Side-effect imports are never elided.
+fn is_elided(import: &ImportInfo, usage: &ModuleUsage) -> bool {
+    if import.is_type_only
+        || import.specifiers.is_empty()
+        || !matches!(import.kind, ImportKind::EsModule | ImportKind::ImportEquals)
+    {
+        return false;
+    }
+
+    // The classic JSX runtime compiles `<div />` to `React.createElement`
+    let read_as_value =
+        |local: &str| usage.value_refs.contains(local) || (usage.has_jsx && local == "React");
+
+    !import
+        .specifiers
+        .iter()
+        .any(|b| !b.is_type_only && read_as_value(&b.local))
+}
+
+/// Decide whether an import's bindings are read during module evaluation.
+///
+/// Side-effect imports and re-exports are treated as eager, since they tie the
+/// evaluation order of both modules together.
+fn evaluation_for(import: &ImportInfo, span: Span, usage: &ModuleUsage) -> Evaluation {
+    let read_eagerly = |bindings: &[ImportedBinding]| {
+        bindings
+            .iter()
+            .any(|b| !b.is_type_only && usage.eager_refs.contains(&b.local))
+    };
+
+    let eager = if import.is_type_only || import.is_elided {
+        false
+    } else {
+        match import.kind {
+            // Mock factories and `requireActual` run when the test framework asks for the module
+            ImportKind::Dynamic | ImportKind::TripleSlash | ImportKind::Mock => false,
+            // Workers evaluate in their own realm
+            ImportKind::Worker => false,
+            ImportKind::Glob => {
+                import.glob.as_ref().is_some_and(|g| g.eager)
+                    && usage.eager_calls.contains(&span.lo)
+            }
+            ImportKind::ReExport => true,
+            ImportKind::EsModule => {
+                import.specifiers.is_empty() || read_eagerly(&import.specifiers)
+            }
+            ImportKind::ImportEquals => read_eagerly(&import.specifiers),
+            // `const { a } = require('./a')` reads `a` immediately;
+            // `const a = require('./a')` only when `a` itself is read
+            ImportKind::CommonJs => {
+                usage.eager_calls.contains(&span.lo)
+                    && match import.specifiers.as_slice() {
+                        [ns] if ns.kind == SpecifierKind::Namespace => {
+                            usage.eager_refs.contains(&ns.local)
+                        }
+                        _ => true,
+                    }
+            }
+        }
+    };
+
+    if eager {
+        Evaluation::Eager
+    } else {
+        Evaluation::Deferred
+    }
+}
+
+fn export_name(name: &ModuleExportName) -> String {
+    match name {
+        ModuleExportName::Ident(ident) => ident.sym.to_string(),
+        ModuleExportName::Str(s) => s.value.to_string(),
+    }
+}
+
+/// Bindings introduced by an import declaration
+fn import_decl_bindings(import_decl: &ImportDecl) -> Vec<ImportedBinding> {
+    import_decl
+        .specifiers
+        .iter()
+        .map(|spec| match spec {
+            ImportSpecifier::Named(named) => {
+                let local = named.local.sym.to_string();
+                let imported = named.imported.as_ref().map_or(local.clone(), export_name);
+                ImportedBinding::new(
+                    SpecifierKind::Named,
+                    imported,
+                    local,
+                    import_decl.type_only || named.is_type_only,
+                )
+            }
+            ImportSpecifier::Default(default) => ImportedBinding::new(
+                SpecifierKind::Default,
+                "default".to_string(),
+                default.local.sym.to_string(),
+                import_decl.type_only,
+            ),
+            ImportSpecifier::Namespace(ns) => ImportedBinding::new(
+                SpecifierKind::Namespace,
+                "*".to_string(),
+                ns.local.sym.to_string(),
+                import_decl.type_only,
+            ),
+        })
+        .collect()
+}
+
+/// Bindings re-exported by `export { ... } from './foo'`
+fn named_export_bindings(named_export: &NamedExport) -> Vec<ImportedBinding> {
+    named_export
+        .specifiers
+        .iter()
+        .map(|spec| match spec {
+            ExportSpecifier::Named(named) => {
+                let imported = export_name(&named.orig);
+                let local = named
+                    .exported
+                    .as_ref()
+                    .map_or(imported.clone(), export_name);
+                let kind = if imported == "default" {
+                    SpecifierKind::Default
+                } else {
+                    SpecifierKind::Named
+                };
+                ImportedBinding::new(
+                    kind,
+                    imported,
+                    local,
+                    named_export.type_only || named.is_type_only,
+                )
+            }
+            ExportSpecifier::Namespace(ns) => ImportedBinding::new(
+                SpecifierKind::Namespace,
+                "*".to_string(),
+                export_name(&ns.name),
+                named_export.type_only,
+            ),
+            ExportSpecifier::Default(default) => ImportedBinding::new(
+                SpecifierKind::Default,
+                "default".to_string(),
+                default.exported.sym.to_string(),
+                named_export.type_only,
+            ),
+        })
+        .collect()
+}
+
+/// Bindings introduced by assigning a `require()`/`import()` result to a pattern:
+/// `const foo = require('./foo')` or `const { a, b: c } = await import('./foo')`
+fn pattern_bindings(pat: &Pat) -> Vec<ImportedBinding> {
+    match pat {
+        Pat::Ident(ident) => vec![ImportedBinding::new(
+            SpecifierKind::Namespace,
+            "*".to_string(),
+            ident.id.sym.to_string(),
+            false,
+        )],
+        Pat::Object(obj) => obj
+            .props
+            .iter()
+            .filter_map(|prop| match prop {
+                ObjectPatProp::Assign(assign) => {
+                    let name = assign.key.sym.to_string();
+                    Some(ImportedBinding::new(
+                        SpecifierKind::Named,
+                        name.clone(),
+                        name,
+                        false,
+                    ))
+                }
+                ObjectPatProp::KeyValue(kv) => {
+                    let imported = match &kv.key {
+                        PropName::Ident(ident) => ident.sym.to_string(),
+                        PropName::Str(s) => s.value.to_string(),
+                        _ => return None,
+                    };
+                    let local = match &*kv.value {
+                        Pat::Ident(ident) => ident.id.sym.to_string(),
+                        _ => imported.clone(),
+                    };
+                    Some(ImportedBinding::new(
+                        SpecifierKind::Named,
+                        imported,
+                        local,
+                        false,
+                    ))
+                }
+                ObjectPatProp::Rest(_) => None,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Returns the `require()` or `import()` call an initializer evaluates to, if any.
+fn module_call(expr: &Expr) -> Option<&CallExpr> {
+    match expr {
+        Expr::Await(await_expr) => module_call(&await_expr.arg),
+        Expr::Paren(paren) => module_call(&paren.expr),
+        Expr::Call(call) => match &call.callee {
+            Callee::Import(_) => Some(call),
+            Callee::Expr(callee) if matches!(&**callee, Expr::Ident(i) if i.sym.as_ref() == "require") => {
+                Some(call)
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The module specifier passed to `require()` or `import()`
+enum CallSpecifier {
+    /// A string literal (or concatenation of literals)
+    Static(String),
+    /// A computed specifier with a static relative prefix, as a glob: `./locales/*.ts`
+    Pattern(String),
+}
+
+/// Reads a call's specifier argument. Template literals and `+` concatenations are
+/// supported when they start with a relative path; each expression becomes a `*`.
+fn call_specifier(expr: &Expr) -> Option<CallSpecifier> {
+    let mut parts = Vec::new();
+    specifier_parts(expr, &mut parts);
+
+    if parts.iter().all(Option::is_some) {
+        return Some(CallSpecifier::Static(parts.into_iter().flatten().collect()));
+    }
+
+    let prefix = parts.first()?.as_deref()?;
+    if !prefix.starts_with("./") && !prefix.starts_with("../") {
+        return None;
+    }
+
+    let mut pattern = String::new();
+    for part in parts {
+        match part {
+            Some(text) => pattern.push_str(&Pattern::escape(&text)),
+            None if !pattern.ends_with('*') => pattern.push('*'),
+            None => {}
+        }
+    }
+    Some(CallSpecifier::Pattern(pattern))
+}
+
+/// Flattens a specifier into static text (`Some`) and dynamic expressions (`None`)
+fn specifier_parts(expr: &Expr, parts: &mut Vec<Option<String>>) {
+    match expr {
+        Expr::Lit(Lit::Str(s)) => parts.push(Some(s.value.to_string())),
+        Expr::Paren(paren) => specifier_parts(&paren.expr, parts),
+        Expr::Bin(bin) if bin.op == BinaryOp::Add => {
+            specifier_parts(&bin.left, parts);
+            specifier_parts(&bin.right, parts);
+        }
+        Expr::Tpl(tpl) => {
+            for (i, quasi) in tpl.quasis.iter().enumerate() {
+                match &quasi.cooked {
+                    Some(cooked) => parts.push(Some(cooked.to_string())),
+                    None => parts.push(None),
+                }
+                if let Some(expr) = tpl.exprs.get(i) {
+                    specifier_parts(expr, parts);
+                }
+            }
+        }
+        _ => parts.push(None),
+    }
+}
+
+/// Jest and Vitest functions that take a module path as their first argument
+const MOCK_FUNCTIONS: &[(&str, &[&str])] = &[
+    (
+        "jest",
+        &[
+            "mock",
+            "unmock",
+            "doMock",
+            "dontMock",
+            "setMock",
+            "requireActual",
+            "requireMock",
+            "createMockFromModule",
+        ],
+    ),
+    (
+        "vi",
+        &[
+            "mock",
+            "unmock",
+            "doMock",
+            "doUnmock",
+            "importActual",
+            "importMock",
+        ],
+    ),
+];
+
+/// Returns the module path of a Jest/Vitest mock call like `jest.mock('./db')`.
+fn mock_source(call: &CallExpr) -> Option<String> {
+    let Callee::Expr(callee) = &call.callee else {
+        return None;
+    };
+    let Expr::Member(member) = &**callee else {
+        return None;
+    };
+    let (Expr::Ident(object), MemberProp::Ident(method)) = (&*member.obj, &member.prop) else {
+        return None;
+    };
+
+    let is_mock = MOCK_FUNCTIONS.iter().any(|(framework, methods)| {
+        object.sym.as_ref() == *framework && methods.contains(&method.sym.as_ref())
+    });
+    if !is_mock {
+        return None;
+    }
+
+    match &*call.args.first()?.expr {
+        Expr::Lit(Lit::Str(s)) => Some(s.value.to_string()),
+        _ => None,
+    }
+}
+
+/// Returns the path of a `new URL('./worker.ts', import.meta.url)` expression.
+fn module_url(expr: &Expr) -> Option<String> {
+    let Expr::New(new_expr) = expr else {
+        return None;
+    };
+    if !matches!(&*new_expr.callee, Expr::Ident(i) if i.sym.as_ref() == "URL") {
+        return None;
+    }
+
+    let args = new_expr.args.as_ref()?;
+    let base_is_import_meta = args.get(1).is_some_and(|arg| {
+        matches!(
+            &*arg.expr,
+            Expr::Member(member)
+                if matches!(&*member.obj, Expr::MetaProp(meta) if meta.kind == MetaPropKind::ImportMeta)
+                    && matches!(&member.prop, MemberProp::Ident(p) if p.sym.as_ref() == "url")
+        )
+    });
+    if !base_is_import_meta {
+        return None;
+    }
+
+    match &*args.first()?.expr {
+        Expr::Lit(Lit::Str(s)) => Some(s.value.to_string()),
+        _ => None,
+    }
+}
+
+/// What a bundler query on an import specifier loads
+#[derive(Debug, PartialEq, Eq)]
+enum QueryKind {
+    /// The module itself (no query, or one that doesn't change what's loaded)
+    Module,
+    /// A worker running the module: `./resize.ts?worker`, `?sharedworker`
+    Worker,
+    /// The file's URL or contents, without evaluating it: `?url`, `?raw`, `?inline`
+    Asset,
+}
+
+/// Classifies a Vite-style query (`./resize.ts?worker&inline`)
+fn query_kind(source: &str) -> QueryKind {
+    let Some((_, query)) = source.split_once('?') else {
+        return QueryKind::Module;
+    };
+    let query = query.split('#').next().unwrap_or_default();
+    let keys: Vec<&str> = query
+        .split('&')
+        .map(|param| param.split('=').next().unwrap_or_default())
+        .collect();
+
+    if keys
+        .iter()
+        .any(|key| matches!(*key, "worker" | "sharedworker"))
+    {
+        QueryKind::Worker
+    } else if keys
+        .iter()
+        .any(|key| matches!(*key, "url" | "raw" | "inline"))
+    {
+        QueryKind::Asset
+    } else {
+        QueryKind::Module
+    }
+}
+
+/// Whether a call is `navigator.serviceWorker.register(...)` (or `serviceWorker.register(...)`)
+fn is_service_worker_register(call: &CallExpr) -> bool {
+    let Callee::Expr(callee) = &call.callee else {
+        return false;
+    };
+    let Expr::Member(member) = &**callee else {
+        return false;
+    };
+    if !matches!(&member.prop, MemberProp::Ident(p) if p.sym.as_ref() == "register") {
+        return false;
+    }
+
+    match &*member.obj {
+        Expr::Ident(ident) => ident.sym.as_ref() == "serviceWorker",
+        Expr::Member(inner) => {
+            matches!(&inner.prop, MemberProp::Ident(p) if p.sym.as_ref() == "serviceWorker")
+        }
+        _ => false,
+    }
+}
+
+/// Parses `import.meta.glob(patterns, options)` (and the deprecated `import.meta.globEager`).
+fn vite_glob(call: &CallExpr) -> Option<GlobImport> {
+    let Callee::Expr(callee) = &call.callee else {
+        return None;
+    };
+    let Expr::Member(member) = &**callee else {
+        return None;
+    };
+    let is_import_meta = matches!(
+        &*member.obj,
+        Expr::MetaProp(meta) if meta.kind == MetaPropKind::ImportMeta
+    );
+    let method = match &member.prop {
+        MemberProp::Ident(ident) if is_import_meta => ident.sym.as_ref(),
+        _ => return None,
+    };
+    if method != "glob" && method != "globEager" {
+        return None;
+    }
+
+    let patterns = match &*call.args.first()?.expr {
+        Expr::Lit(Lit::Str(s)) => vec![s.value.to_string()],
+        Expr::Array(array) => array
+            .elems
+            .iter()
+            .flatten()
+            .filter_map(|elem| match &*elem.expr {
+                Expr::Lit(Lit::Str(s)) => Some(s.value.to_string()),
+                _ => None,
+            })
+            .collect(),
+        _ => return None,
+    };
+
+    // `{ eager: true }` makes Vite emit static imports instead of lazy loaders
+    let eager = method == "globEager" || call.args.get(1).is_some_and(has_eager_option);
+
+    Some(GlobImport {
+        patterns,
+        filter: None,
+        eager,
+    })
+}
+
+/// Whether an `import.meta.glob` options argument sets `eager: true`.
+fn has_eager_option(arg: &ExprOrSpread) -> bool {
+    let Expr::Object(obj) = &*arg.expr else {
+        return false;
+    };
+    obj.props.iter().any(|prop| match prop {
+        PropOrSpread::Prop(prop) => match &**prop {
+            Prop::KeyValue(kv) => {
+                matches!(&kv.key, PropName::Ident(key) if key.sym.as_ref() == "eager")
+                    && matches!(&*kv.value, Expr::Lit(Lit::Bool(b)) if b.value)
+            }
+            _ => false,
+        },
+        PropOrSpread::Spread(_) => false,
+    })
+}
+
+/// Parses webpack's `require.context(directory, useSubdirectories, regExp, mode)`.
+fn require_context(call: &CallExpr) -> Option<GlobImport> {
+    let Callee::Expr(callee) = &call.callee else {
+        return None;
+    };
+    let Expr::Member(member) = &**callee else {
+        return None;
+    };
+    let is_require = matches!(&*member.obj, Expr::Ident(i) if i.sym.as_ref() == "require");
+    if !is_require || !matches!(&member.prop, MemberProp::Ident(p) if p.sym.as_ref() == "context") {
+        return None;
     }
+
+    let directory = match &*call.args.first()?.expr {
+        Expr::Lit(Lit::Str(s)) => s.value.trim_end_matches('/').to_string(),
+        _ => return None,
+    };
+    let recursive = match call.args.get(1).map(|arg| &*arg.expr) {
+        Some(Expr::Lit(Lit::Bool(b))) => b.value,
+        _ => true,
+    };
+    let filter = match call.args.get(2).map(|arg| &*arg.expr) {
+        Some(Expr::Lit(Lit::Regex(re))) if re.flags.contains('i') => {
+            Some(format!("(?i){}", re.exp))
+        }
+        Some(Expr::Lit(Lit::Regex(re))) => Some(re.exp.to_string()),
+        _ => None,
+    };
+    // Only the default "sync" mode loads every module up front
+    let eager = match call.args.get(3).map(|arg| &*arg.expr) {
+        Some(Expr::Lit(Lit::Str(mode))) => mode.value.as_ref() == "sync",
+        _ => true,
+    };
+
+    let pattern = if recursive { "**/*" } else { "*" };
+    Some(GlobImport {
+        patterns: vec![format!("{}/{}", directory, pattern)],
+        filter,
+        eager,
+    })
 }
 
 impl Visit for ImportCollector {
@@ -233,25 +1110,52 @@ impl Visit for ImportCollector {
     fn visit_module_item(&mut self, item: &ModuleItem) {
         match item {
             // ES Module imports: import { foo } from './foo'
-            ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) => {
-                if self.should_include_import(import_decl) {
-                    self.add_import(
-                        import_decl.src.value.to_string(),
-                        import_decl.span,
-                        import_decl.type_only,
-                        ImportKind::EsModule,
-                    );
-                }
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl))
+                if self.should_include_import(import_decl) =>
+            {
+                self.add_import(
+                    import_decl.src.value.to_string(),
+                    import_decl.span,
+                    import_decl.type_only,
+                    ImportKind::EsModule,
+                    import_decl_bindings(import_decl),
+                );
             }
             // Re-exports: export * from './foo'
-            ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export_all)) => {
-                // export type * from './foo' is type-only
-                if !(self.ignore_type_imports && export_all.type_only) {
-                    self.add_import(
-                        export_all.src.value.to_string(),
-                        export_all.span,
+            // export type * from './foo' is type-only
+            ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export_all))
+                if !(self.ignore_type_imports && export_all.type_only) =>
+            {
+                self.add_import(
+                    export_all.src.value.to_string(),
+                    export_all.span,
+                    export_all.type_only,
+                    ImportKind::ReExport,
+                    vec![ImportedBinding::new(
+                        SpecifierKind::Namespace,
+                        "*".to_string(),
+                        "*".to_string(),
                         export_all.type_only,
-                        ImportKind::ReExport,
+                    )],
+                );
+            }
+            // TypeScript import-equals: import foo = require('./foo')
+            // `import type foo = require('./foo')` is type-only
+            ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(import_equals))
+                if !(self.ignore_type_imports && import_equals.is_type_only) =>
+            {
+                if let TsModuleRef::TsExternalModuleRef(module_ref) = &import_equals.module_ref {
+                    self.add_import(
+                        module_ref.expr.value.to_string(),
+                        import_equals.span,
+                        import_equals.is_type_only,
+                        ImportKind::ImportEquals,
+                        vec![ImportedBinding::new(
+                            SpecifierKind::Namespace,
+                            "*".to_string(),
+                            import_equals.id.sym.to_string(),
+                            import_equals.is_type_only,
+                        )],
                     );
                 }
             }
@@ -265,6 +1169,7 @@ impl Visit for ImportCollector {
                             named_export.span,
                             named_export.type_only,
                             ImportKind::ReExport,
+                            named_export_bindings(named_export),
                         );
                     }
                 }
@@ -275,21 +1180,34 @@ impl Visit for ImportCollector {
         item.visit_children_with(self);
     }
 
-    // Handle CommonJS require() calls
+    // Record the bindings of `const x = require('./foo')` for the call visited below
+    fn visit_var_declarator(&mut self, decl: &VarDeclarator) {
+        if let Some(call) = decl.init.as_deref().and_then(module_call) {
+            self.call_bindings
+                .insert(call.span.lo, pattern_bindings(&decl.name));
+        }
+
+        decl.visit_children_with(self);
+    }
+
+    // Handle CommonJS require() calls, bundler glob imports and test mocks
     fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let Some(glob) = vite_glob(call).or_else(|| require_context(call)) {
+            self.add_glob_import(call.span, glob);
+        }
+        if let Some(source) = mock_source(call) {
+            self.add_import(source, call.span, false, ImportKind::Mock, vec![]);
+        }
+        if is_service_worker_register(call) {
+            if let Some(source) = call.args.first().and_then(|arg| module_url(&arg.expr)) {
+                self.add_import(source, call.span, false, ImportKind::Worker, vec![]);
+            }
+        }
+
         if let Callee::Expr(expr) = &call.callee {
             if let Expr::Ident(ident) = &**expr {
                 if ident.sym.as_ref() == "require" {
-                    if let Some(arg) = call.args.first() {
-                        if let Expr::Lit(Lit::Str(s)) = &*arg.expr {
-                            self.add_import(
-                                s.value.to_string(),
-                                call.span,
-                                false,
-                                ImportKind::CommonJs,
-                            );
-                        }
-                    }
+                    self.add_call_import(call, ImportKind::CommonJs);
                 }
             }
         }
@@ -298,15 +1216,31 @@ impl Visit for ImportCollector {
         call.visit_children_with(self);
     }
 
-    // Handle dynamic imports: import('./foo')
+    // Handle workers: new Worker(new URL('./worker.ts', import.meta.url))
+    fn visit_new_expr(&mut self, new_expr: &NewExpr) {
+        let is_worker = matches!(
+            &*new_expr.callee,
+            Expr::Ident(i) if matches!(i.sym.as_ref(), "Worker" | "SharedWorker")
+        );
+        if is_worker {
+            let url = new_expr
+                .args
+                .as_ref()
+                .and_then(|args| args.first())
+                .and_then(|arg| module_url(&arg.expr));
+            if let Some(source) = url {
+                self.add_import(source, new_expr.span, false, ImportKind::Worker, vec![]);
+            }
+        }
+
+        new_expr.visit_children_with(self);
+    }
+
+    // Handle dynamic imports: import('./foo') or import(`./locales/${lang}.ts`)
     fn visit_expr(&mut self, expr: &Expr) {
         if let Expr::Call(call) = expr {
             if let Callee::Import(_) = &call.callee {
-                if let Some(arg) = call.args.first() {
-                    if let Expr::Lit(Lit::Str(s)) = &*arg.expr {
-                        self.add_import(s.value.to_string(), call.span, false, ImportKind::Dynamic);
-                    }
-                }
+                self.add_call_import(call, ImportKind::Dynamic);
             }
         }
 
@@ -355,6 +1289,7 @@ mod tests {
             file.path(),
             &ParserOptions {
                 ignore_type_imports: true,
+                ..Default::default()
             },
         );
         assert_eq!(imports.len(), 0);
@@ -411,4 +1346,323 @@ mod tests {
         assert_eq!(imports.len(), 1);
         assert_eq!(imports[0].import_text, "import { foo, bar } from './baz';");
     }
+
+    #[test]
+    fn test_parse_vue_component() {
+        let file = create_temp_file(
+            "<template>\n  <Child />\n</template>\n\n<script setup lang=\"ts\">\nimport type { Props } from './types';\nimport Child from './Child.vue';\n</script>\n",
+            ".vue",
+        );
+        let imports = get_imports_from_file(file.path(), &ParserOptions::default());
+
+        assert_eq!(imports.len(), 2);
+        assert!(imports[0].is_type_only);
+        assert_eq!(imports[1].source, "./Child.vue");
+        assert_eq!(imports[1].line, 7);
+        assert_eq!(imports[1].import_text, "import Child from './Child.vue';");
+    }
+
+    #[test]
+    fn test_specifiers_captured() {
+        let file = create_temp_file(
+            "import React, { useState as useS, type FC } from 'react';\nimport * as ns from './ns';\nexport { default as Button, helper } from './button';",
+            ".ts",
+        );
+        let imports = get_imports_from_file(file.path(), &ParserOptions::default());
+
+        assert_eq!(imports.len(), 3);
+        let names: Vec<String> = imports[0]
+            .specifiers
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["default as React", "useState as useS", "type FC"]
+        );
+        assert_eq!(imports[0].specifiers[0].kind, SpecifierKind::Default);
+        assert!(imports[0].specifiers[2].is_type_only);
+
+        assert_eq!(imports[1].specifiers[0].kind, SpecifierKind::Namespace);
+        assert_eq!(imports[1].specifiers[0].to_string(), "* as ns");
+
+        let names: Vec<String> = imports[2]
+            .specifiers
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(names, vec!["default as Button", "helper"]);
+    }
+
+    #[test]
+    fn test_require_specifiers_captured() {
+        let file = create_temp_file(
+            "const { a, b: c } = require('./x');\nconst y = require('./y');\nrequire('./side-effect');",
+            ".js",
+        );
+        let imports = get_imports_from_file(file.path(), &ParserOptions::default());
+
+        assert_eq!(imports.len(), 3);
+        let names: Vec<String> = imports[0]
+            .specifiers
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(names, vec!["a", "b as c"]);
+        assert_eq!(imports[1].specifiers[0].to_string(), "* as y");
+        assert!(imports[2].specifiers.is_empty());
+    }
+
+    #[test]
+    fn test_evaluation_classification() {
+        let file = create_temp_file(
+            "import { Base } from './base';\nimport { helper } from './helper';\nimport type { T } from './types';\nimport './polyfill';\nconst lazy = () => import('./lazy');\nconst cfg = require('./config');\nexport class A extends Base { run(): T { return helper(cfg.value); } }\nfunction load() { return require('./late'); }",
+            ".ts",
+        );
+        let imports = get_imports_from_file(file.path(), &ParserOptions::default());
+        let evaluation = |source: &str| {
+            imports
+                .iter()
+                .find(|i| i.source == source)
+                .map(|i| i.evaluation)
+                .unwrap()
+        };
+
+        assert_eq!(evaluation("./base"), Evaluation::Eager);
+        assert_eq!(evaluation("./helper"), Evaluation::Deferred);
+        assert_eq!(evaluation("./types"), Evaluation::Deferred);
+        assert_eq!(evaluation("./polyfill"), Evaluation::Eager);
+        assert_eq!(evaluation("./lazy"), Evaluation::Deferred);
+        assert_eq!(evaluation("./config"), Evaluation::Deferred);
+        assert_eq!(evaluation("./late"), Evaluation::Deferred);
+    }
+
+    #[test]
+    fn test_type_usage_imports_are_elided() {
+        let source = "import { User } from './user';\nimport { Service, type Options } from './service';\nimport { render } from './render';\nimport React from 'react';\nimport './styles';\nexport function f(u: User, o: Options): typeof Service | null { return render(<div />); }";
+        let file = create_temp_file(source, ".tsx");
+
+        let imports = get_imports_from_file(file.path(), &ParserOptions::default());
+        let elided: Vec<&str> = imports
+            .iter()
+            .filter(|i| i.is_elided)
+            .map(|i| i.source.as_str())
+            .collect();
+        assert_eq!(elided, vec!["./user", "./service"]);
+
+        let imports = get_imports_from_file(
+            file.path(),
+            &ParserOptions {
+                ignore_elided_imports: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(imports.len(), 3);
+
+        // Plain JavaScript is never elided
+        let file = create_temp_file("import { User } from './user';", ".js");
+        let imports = get_imports_from_file(file.path(), &ParserOptions::default());
+        assert!(!imports[0].is_elided);
+    }
+
+    #[test]
+    fn test_parse_glob_imports() {
+        let source = r#"const pages = import.meta.glob(['./pages/*.tsx', '!./pages/*.test.tsx']);
+const eager = import.meta.glob('./locales/*.json', { eager: true });
+const ctx = require.context('./modules/', false, /\.js$/i);"#;
+        let file = create_temp_file(source, ".ts");
+        let imports = get_imports_from_file(file.path(), &ParserOptions::default());
+
+        assert_eq!(imports.len(), 3);
+        assert!(imports.iter().all(|i| i.kind == ImportKind::Glob));
+
+        let pages = imports[0].glob.as_ref().unwrap();
+        assert_eq!(pages.patterns, vec!["./pages/*.tsx", "!./pages/*.test.tsx"]);
+        assert_eq!(imports[0].source, "./pages/*.tsx");
+        assert_eq!(imports[0].evaluation, Evaluation::Deferred);
+        assert_eq!(imports[1].evaluation, Evaluation::Eager);
+
+        let ctx = imports[2].glob.as_ref().unwrap();
+        assert_eq!(ctx.patterns, vec!["./modules/*"]);
+        assert_eq!(ctx.filter.as_deref(), Some(r"(?i)\.js$"));
+        assert_eq!(imports[2].line, 3);
+    }
+
+    #[test]
+    fn test_computed_specifiers_become_globs() {
+        let source = r#"import(`./locales/${lang}.ts`);
+require('./handlers/' + name + '.js');
+require('./static' + '.js');
+require(name);
+import(`${base}/x.ts`);"#;
+        let file = create_temp_file(source, ".ts");
+        let imports = get_imports_from_file(file.path(), &ParserOptions::default());
+
+        assert_eq!(imports.len(), 3);
+        assert_eq!(imports[0].kind, ImportKind::Dynamic);
+        assert_eq!(imports[0].source, "./locales/*.ts");
+        assert!(imports[0].is_speculative);
+        assert_eq!(
+            imports[1].glob.as_ref().unwrap().patterns,
+            vec!["./handlers/*.js"]
+        );
+        assert_eq!(imports[2].source, "./static.js");
+        assert!(!imports[2].is_speculative);
+        assert!(imports[2].glob.is_none());
+    }
+
+    #[test]
+    fn test_parse_mock_calls() {
+        let source = r#"import { save } from './db';
+jest.mock('./db');
+vi.mock('../api', () => ({ fetch: vi.fn() }));
+const actual = jest.requireActual('./utils');
+jest.fn('./not-a-module');"#;
+        let file = create_temp_file(source, ".test.ts");
+        let imports = get_imports_from_file(file.path(), &ParserOptions::default());
+
+        let mocks: Vec<&str> = imports
+            .iter()
+            .filter(|i| i.kind == ImportKind::Mock)
+            .map(|i| i.source.as_str())
+            .collect();
+        assert_eq!(mocks, vec!["./db", "../api", "./utils"]);
+        assert!(imports
+            .iter()
+            .filter(|i| i.kind == ImportKind::Mock)
+            .all(|i| i.evaluation == Evaluation::Deferred));
+    }
+
+    #[test]
+    fn test_parse_worker_urls() {
+        let source = r#"const worker = new Worker(new URL('./worker.ts', import.meta.url), { type: 'module' });
+const shared = new SharedWorker(new URL('./shared.js', import.meta.url));
+navigator.serviceWorker.register(new URL('./sw.ts', import.meta.url));
+const asset = new URL('./logo.svg', import.meta.url);
+new Worker('/static/legacy.js');"#;
+        let file = create_temp_file(source, ".ts");
+        let imports = get_imports_from_file(file.path(), &ParserOptions::default());
+
+        let sources: Vec<&str> = imports.iter().map(|i| i.source.as_str()).collect();
+        assert_eq!(sources, vec!["./worker.ts", "./shared.js", "./sw.ts"]);
+        assert!(imports.iter().all(|i| i.kind == ImportKind::Worker));
+        assert!(imports.iter().all(|i| i.evaluation == Evaluation::Deferred));
+    }
+
+    #[test]
+    fn test_bundler_queries() {
+        let source = r#"import Resize from './resize.ts?worker';
+import Shared from './shared.ts?sharedworker&inline';
+import logoUrl from './logo.svg?url';
+import changelog from './CHANGELOG.md?raw';
+import styles from './app.css?inline';
+import icon from './icons.svg#close';
+export { default as Lazy } from './lazy.ts?worker';
+const page = import('./page.ts?import');"#;
+        let file = create_temp_file(source, ".ts");
+        let imports = get_imports_from_file(file.path(), &ParserOptions::default());
+
+        let kinds: Vec<(&str, &ImportKind)> = imports
+            .iter()
+            .map(|i| (i.source.as_str(), &i.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("./resize.ts?worker", &ImportKind::Worker),
+                ("./shared.ts?sharedworker&inline", &ImportKind::Worker),
+                ("./icons.svg#close", &ImportKind::EsModule),
+                ("./lazy.ts?worker", &ImportKind::Worker),
+                ("./page.ts?import", &ImportKind::Dynamic),
+            ]
+        );
+        assert_eq!(imports[0].evaluation, Evaluation::Deferred);
+    }
+
+    #[test]
+    fn test_parse_import_equals() {
+        let file = create_temp_file(
+            "import foo = require('./foo');\nimport type Bar = require('./bar');\nexport import baz = require('./baz');\nimport Alias = Foo.Bar;\nexport = foo;",
+            ".ts",
+        );
+
+        let imports = get_imports_from_file(file.path(), &ParserOptions::default());
+        assert_eq!(imports.len(), 3);
+        assert!(imports.iter().all(|i| i.kind == ImportKind::ImportEquals));
+        assert_eq!(imports[0].source, "./foo");
+        assert!(!imports[0].is_type_only);
+        assert!(imports[1].is_type_only);
+        assert_eq!(imports[2].source, "./baz");
+
+        let imports = get_imports_from_file(
+            file.path(),
+            &ParserOptions {
+                ignore_type_imports: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(imports.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_triple_slash_reference() {
+        let file = create_temp_file(
+            "/// <reference path=\"./types.d.ts\" />\n/// <reference types=\"node\" />\nimport { a } from './a';\n/// <reference path=\"./ignored.d.ts\" />",
+            ".ts",
+        );
+
+        let imports = get_imports_from_file(file.path(), &ParserOptions::default());
+        assert_eq!(imports.len(), 2);
+        assert_eq!(imports[0].source, "./types.d.ts");
+        assert_eq!(imports[0].kind, ImportKind::TripleSlash);
+        assert_eq!(imports[0].line, 1);
+        assert!(imports[0].is_type_only);
+        assert_eq!(
+            imports[0].import_text,
+            "/// <reference path=\"./types.d.ts\" />"
+        );
+        assert_eq!(imports[1].source, "./a");
+    }
+
+    #[test]
+    fn test_triple_slash_reference_relative_without_dot() {
+        let file = create_temp_file("/// <reference path=\"types.d.ts\" />\n", ".ts");
+
+        let imports = get_imports_from_file(file.path(), &ParserOptions::default());
+        assert_eq!(imports.len(), 1);
+        assert_eq!(imports[0].source, "./types.d.ts");
+    }
+
+    #[test]
+    fn test_parse_mts_file() {
+        let file = create_temp_file(
+            "import { a } from './a.mjs';\nconst x = <T,>(v: T) => v;",
+            ".mts",
+        );
+        let imports = get_imports_from_file(file.path(), &ParserOptions::default());
+
+        assert_eq!(imports.len(), 1);
+        assert_eq!(imports[0].source, "./a.mjs");
+    }
+
+    #[test]
+    fn test_declaration_file_imports_are_type_only() {
+        let file = create_temp_file(
+            "import { User } from './user';\ndeclare module 'foo' {\n  export function bar(): User;\n}",
+            ".d.ts",
+        );
+
+        let imports = get_imports_from_file(file.path(), &ParserOptions::default());
+        assert_eq!(imports.len(), 1);
+        assert!(imports[0].is_type_only);
+
+        let imports = get_imports_from_file(
+            file.path(),
+            &ParserOptions {
+                ignore_type_imports: true,
+                ..Default::default()
+            },
+        );
+        assert!(imports.is_empty());
+    }
 }
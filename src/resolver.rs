@@ -0,0 +1,694 @@
+//! Resolution of import specifiers to files.
+//!
+//! Imports are resolved by a chain of [`Resolver`]s, tried in order until one
+//! finds a file. The built-in resolvers cover package.json `imports`, relative
+//! paths, aliases (config file, tsconfig and bundler configs), workspace
+//! packages and `node_modules`. The chain is set with `resolvers` in the config
+//! file, and other resolvers can be added to [`ResolveOptions::resolvers`].
+
+use crate::aliases::{AliasMap, AliasTarget, BundlerAliasCache};
+use crate::filesystem::normalize_path;
+use crate::graph::{ModuleSuffixes, ResolveOptions, UnresolvedKind};
+use crate::node_modules::{NodeModules, NodeModulesTarget};
+use crate::tsconfig::PathAliases;
+use crate::utils::{clean_path, split_package_name, ts_extensions_for_js, EXTENSIONS};
+use crate::workspace::{ImportsTarget, PackageImports, Workspace};
+use log::debug;
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// The built-in resolvers, in their default order.
+pub const DEFAULT_RESOLVERS: &[&str] = &[
+    "package-imports",
+    "relative",
+    "aliases",
+    "tsconfig",
+    "bundler-aliases",
+    "workspace",
+    "node-modules",
+];
+
+/// How many aliases (or `imports` targets naming a package) are followed in a row
+const MAX_DEPTH: usize = 8;
+
+/// A strategy for resolving import specifiers to files.
+pub trait Resolver: Debug + Send + Sync {
+    /// Name used in the `resolvers` config and recorded on each edge
+    fn name(&self) -> &'static str;
+
+    /// Returns the files `request` resolves to, or an empty list to let the next
+    /// resolver try. Several files are returned only for `moduleSuffixes`
+    /// variants with [`ModuleSuffixes::All`].
+    fn resolve(&self, ctx: &ResolveContext, request: &ResolveRequest) -> Vec<PathBuf>;
+}
+
+/// An import to resolve.
+#[derive(Debug, Clone, Copy)]
+pub struct ResolveRequest<'a> {
+    /// The importing file
+    pub importer: &'a Path,
+    /// The import specifier (e.g. "./utils" or "@acme/ui")
+    pub specifier: &'a str,
+    /// The tsconfig that owns the importing file
+    pub path_aliases: Option<&'a PathAliases>,
+    /// Number of aliases followed to get here
+    depth: usize,
+}
+
+impl<'a> ResolveRequest<'a> {
+    pub fn new(
+        importer: &'a Path,
+        specifier: &'a str,
+        path_aliases: Option<&'a PathAliases>,
+    ) -> Self {
+        Self {
+            importer,
+            specifier,
+            path_aliases,
+            depth: 0,
+        }
+    }
+}
+
+/// The files an import resolved to, and the resolver that found them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolved {
+    pub paths: Vec<PathBuf>,
+    pub resolver: &'static str,
+}
+
+/// Everything imports are resolved against, shared by all files of a build.
+pub struct ResolveContext<'a> {
+    pub options: &'a ResolveOptions,
+    pub workspace: Option<&'a Workspace>,
+    pub extensions: Vec<String>,
+    /// `#subpath` imports resolve against each importer's nearest package.json
+    pub(crate) package_imports: PackageImports,
+    /// Bare specifiers not matched by the workspace go through `node_modules`
+    pub(crate) node_modules: NodeModules,
+    /// Vite/webpack/Babel aliases of the nearest bundler config
+    pub(crate) bundler_aliases: BundlerAliasCache,
+}
+
+impl<'a> ResolveContext<'a> {
+    pub(crate) fn new(options: &'a ResolveOptions, workspace: Option<&'a Workspace>) -> Self {
+        Self {
+            options,
+            workspace,
+            // Convert static extensions to owned strings for compatibility
+            extensions: EXTENSIONS.iter().map(|s| s.to_string()).collect(),
+            package_imports: PackageImports::default(),
+            node_modules: NodeModules::default(),
+            bundler_aliases: BundlerAliasCache::default(),
+        }
+    }
+
+    /// Runs the resolver chain. Returns None if no resolver found a file.
+    pub(crate) fn resolve(&self, request: &ResolveRequest) -> Option<Resolved> {
+        debug!(
+            "Attempting to resolve import: '{}' from {:?}",
+            request.specifier, request.importer
+        );
+        for resolver in &self.options.resolvers {
+            let paths = resolver.resolve(self, request);
+            if !paths.is_empty() {
+                debug!(
+                    "Resolved '{}' to {:?} with the {} resolver",
+                    request.specifier,
+                    paths,
+                    resolver.name()
+                );
+                return Some(Resolved {
+                    paths,
+                    resolver: resolver.name(),
+                });
+            }
+        }
+        None
+    }
+
+    /// Resolves another specifier for the same importer, such as an alias target
+    /// naming a package, through the whole chain. Aliases that map onto each
+    /// other give up after a few steps.
+    pub fn resolve_nested(&self, request: &ResolveRequest, specifier: &str) -> Vec<PathBuf> {
+        if request.depth >= MAX_DEPTH {
+            debug!("Giving up on '{}': too many nested aliases", specifier);
+            return Vec::new();
+        }
+        let nested = ResolveRequest {
+            importer: request.importer,
+            specifier,
+            path_aliases: request.path_aliases,
+            depth: request.depth + 1,
+        };
+        self.resolve(&nested)
+            .map(|resolved| resolved.paths)
+            .unwrap_or_default()
+    }
+
+    /// Finds the file a candidate path refers to: extensions, `index` files,
+    /// `.js` -> `.ts` mapping, `outDir` -> source mapping and `moduleSuffixes`.
+    pub fn probe(&self, request: &ResolveRequest, candidate: PathBuf) -> Vec<PathBuf> {
+        check_project_candidates(
+            candidate,
+            &self.extensions,
+            self.options,
+            request.path_aliases,
+        )
+    }
+
+    /// Tells what kind of import no resolver found a file for, or None if it's
+    /// external on purpose: built-ins, protocols (`node:fs`, `virtual:pwa`),
+    /// root-relative public files and packages that aren't part of the project.
+    /// Only the resolvers in the chain are considered, so leaving one out doesn't
+    /// report every import it would have handled.
+    pub(crate) fn classify_unresolved(&self, request: &ResolveRequest) -> Option<UnresolvedKind> {
+        let specifier = request.specifier;
+        if specifier.starts_with('.') {
+            return self.uses("relative").then_some(UnresolvedKind::Relative);
+        }
+        if specifier.starts_with('#') {
+            return self
+                .uses("package-imports")
+                .then_some(UnresolvedKind::Alias);
+        }
+        if self.matches_alias(request) {
+            return Some(UnresolvedKind::Alias);
+        }
+        if specifier.starts_with('/') || specifier.contains(':') {
+            return None;
+        }
+        // A workspace package that exists but doesn't export the import
+        let (name, _) = split_package_name(specifier);
+        let local = self.uses("workspace")
+            && self
+                .workspace
+                .is_some_and(|ws| ws.packages.contains_key(name));
+        local.then_some(UnresolvedKind::Package)
+    }
+
+    /// Whether the resolver with this name is part of the chain.
+    pub fn uses(&self, name: &str) -> bool {
+        self.options.resolvers.iter().any(|r| r.name() == name)
+    }
+
+    /// Whether an alias of the config file, tsconfig or bundler config matches.
+    fn matches_alias(&self, request: &ResolveRequest) -> bool {
+        let specifier = request.specifier;
+        (self.uses("aliases") && !self.options.aliases.resolve(specifier).is_empty())
+            || (self.uses("tsconfig")
+                && request.path_aliases.is_some_and(|aliases| {
+                    aliases
+                        .projects()
+                        .iter()
+                        .any(|project| project.matches_paths(specifier))
+                }))
+            || (self.uses("bundler-aliases")
+                && self
+                    .bundler_aliases
+                    .for_file(request.importer)
+                    .is_some_and(|aliases| !aliases.resolve(specifier).is_empty()))
+    }
+
+    /// Tries the targets of the matching alias in order. Targets naming another
+    /// package go through the chain again.
+    fn resolve_aliases(&self, request: &ResolveRequest, aliases: &AliasMap) -> Vec<PathBuf> {
+        for target in aliases.resolve(request.specifier) {
+            let resolved = match target {
+                AliasTarget::Path(candidate) => self.probe(request, candidate),
+                AliasTarget::Package(package) => self.resolve_nested(request, &package),
+            };
+            if !resolved.is_empty() {
+                return resolved;
+            }
+        }
+        Vec::new()
+    }
+}
+
+/// Drops a bundler query or hash from a specifier (`./logo.svg?url`,
+/// `./icons.svg#close`). A leading `#` is a package.json `imports` specifier.
+pub fn strip_query(specifier: &str) -> &str {
+    let end = specifier
+        .char_indices()
+        .skip(1)
+        .find(|&(_, c)| c == '?' || c == '#')
+        .map_or(specifier.len(), |(i, _)| i);
+    &specifier[..end]
+}
+
+/// Returns the built-in resolver with the given name.
+pub fn builtin_resolver(name: &str) -> Option<Arc<dyn Resolver>> {
+    let resolver: Arc<dyn Resolver> = match name {
+        "package-imports" => Arc::new(PackageImportsResolver),
+        "relative" => Arc::new(RelativeResolver),
+        "aliases" => Arc::new(ConfigAliasResolver),
+        "tsconfig" => Arc::new(TsconfigResolver),
+        "bundler-aliases" => Arc::new(BundlerAliasResolver),
+        "workspace" => Arc::new(WorkspaceResolver),
+        "node-modules" => Arc::new(NodeModulesResolver),
+        _ => return None,
+    };
+    Some(resolver)
+}
+
+/// Builds a chain of built-in resolvers, skipping unknown names.
+pub fn resolvers_from_names<S: AsRef<str>>(names: &[S]) -> Vec<Arc<dyn Resolver>> {
+    names
+        .iter()
+        .filter_map(|name| builtin_resolver(name.as_ref()))
+        .collect()
+}
+
+/// Node subpath imports (`#internal/db`), with the importer's package.json `imports`.
+#[derive(Debug)]
+pub struct PackageImportsResolver;
+
+impl Resolver for PackageImportsResolver {
+    fn name(&self) -> &'static str {
+        "package-imports"
+    }
+
+    fn resolve(&self, ctx: &ResolveContext, request: &ResolveRequest) -> Vec<PathBuf> {
+        if !request.specifier.starts_with('#') {
+            return Vec::new();
+        }
+        // Array fallbacks are tried in order
+        for target in ctx.package_imports.resolve(
+            request.importer,
+            request.specifier,
+            &ctx.options.conditions,
+        ) {
+            let resolved = match target {
+                ImportsTarget::Path(candidate) => ctx.probe(request, candidate),
+                ImportsTarget::Package(package) if !package.starts_with('#') => {
+                    ctx.resolve_nested(request, &package)
+                }
+                ImportsTarget::Package(_) => Vec::new(),
+            };
+            if !resolved.is_empty() {
+                return resolved;
+            }
+        }
+        Vec::new()
+    }
+}
+
+/// Relative imports, also looked up in the other tsconfig `rootDirs`.
+#[derive(Debug)]
+pub struct RelativeResolver;
+
+impl Resolver for RelativeResolver {
+    fn name(&self) -> &'static str {
+        "relative"
+    }
+
+    fn resolve(&self, ctx: &ResolveContext, request: &ResolveRequest) -> Vec<PathBuf> {
+        if !request.specifier.starts_with('.') {
+            return Vec::new();
+        }
+        let Some(dir) = request.importer.parent() else {
+            return Vec::new();
+        };
+        let candidate = dir.join(request.specifier);
+        let virtual_candidates = request
+            .path_aliases
+            .map(|a| a.root_dirs_candidates(&clean_path(&candidate)))
+            .unwrap_or_default();
+        for candidate in std::iter::once(candidate).chain(virtual_candidates) {
+            let resolved = ctx.probe(request, candidate);
+            if !resolved.is_empty() {
+                return resolved;
+            }
+        }
+        Vec::new()
+    }
+}
+
+/// Aliases from the `aliases` map of the config file.
+#[derive(Debug)]
+pub struct ConfigAliasResolver;
+
+impl Resolver for ConfigAliasResolver {
+    fn name(&self) -> &'static str {
+        "aliases"
+    }
+
+    fn resolve(&self, ctx: &ResolveContext, request: &ResolveRequest) -> Vec<PathBuf> {
+        ctx.resolve_aliases(request, &ctx.options.aliases)
+    }
+}
+
+/// tsconfig `paths`/`baseUrl` of the importer's project, then of referenced projects.
+#[derive(Debug)]
+pub struct TsconfigResolver;
+
+impl Resolver for TsconfigResolver {
+    fn name(&self) -> &'static str {
+        "tsconfig"
+    }
+
+    fn resolve(&self, ctx: &ResolveContext, request: &ResolveRequest) -> Vec<PathBuf> {
+        let Some(aliases) = request.path_aliases else {
+            return Vec::new();
+        };
+        for project in aliases.projects() {
+            for candidate in project.resolve_candidates(request.specifier) {
+                let resolved = ctx.probe(request, candidate);
+                if !resolved.is_empty() {
+                    return resolved;
+                }
+            }
+        }
+        Vec::new()
+    }
+}
+
+/// Aliases of the Vite, webpack or Babel config nearest to the importer.
+#[derive(Debug)]
+pub struct BundlerAliasResolver;
+
+impl Resolver for BundlerAliasResolver {
+    fn name(&self) -> &'static str {
+        "bundler-aliases"
+    }
+
+    fn resolve(&self, ctx: &ResolveContext, request: &ResolveRequest) -> Vec<PathBuf> {
+        match ctx.bundler_aliases.for_file(request.importer) {
+            Some(aliases) => ctx.resolve_aliases(request, &aliases),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Packages of the detected workspace.
+#[derive(Debug)]
+pub struct WorkspaceResolver;
+
+impl Resolver for WorkspaceResolver {
+    fn name(&self) -> &'static str {
+        "workspace"
+    }
+
+    fn resolve(&self, ctx: &ResolveContext, request: &ResolveRequest) -> Vec<PathBuf> {
+        let Some(resolved) = ctx
+            .workspace
+            .and_then(|ws| ws.resolve(request.specifier, &ctx.options.conditions))
+        else {
+            return Vec::new();
+        };
+        let normalized = normalize_path(&resolved);
+        debug!(
+            "Resolved workspace import '{}' to {:?}",
+            request.specifier, normalized
+        );
+        vec![normalized]
+    }
+}
+
+/// Node's `node_modules` lookup, following symlinks to linked local packages.
+/// Third-party packages aren't followed.
+#[derive(Debug)]
+pub struct NodeModulesResolver;
+
+impl Resolver for NodeModulesResolver {
+    fn name(&self) -> &'static str {
+        "node-modules"
+    }
+
+    fn resolve(&self, ctx: &ResolveContext, request: &ResolveRequest) -> Vec<PathBuf> {
+        let Some(NodeModulesTarget::Local(resolved)) =
+            ctx.node_modules
+                .resolve(request.importer, request.specifier, &ctx.options.conditions)
+        else {
+            return Vec::new();
+        };
+        let normalized = normalize_path(&resolved);
+        debug!(
+            "Resolved linked package '{}' to {:?}",
+            request.specifier, normalized
+        );
+        vec![normalized]
+    }
+}
+
+fn handle_if_file(path: &Path) -> Option<PathBuf> {
+    if path.is_file() {
+        let canonical = normalize_path(path);
+        debug!("check_candidates: Found file directly {:?}", canonical);
+        return Some(canonical);
+    }
+    None
+}
+
+/// Like [`check_candidates`], but paths inside a project's `outDir` are first
+/// mapped back to their sources, so cycles are reported on the source files.
+/// Each of the project's `moduleSuffixes` (or the target platform's) is tried in order.
+fn check_project_candidates(
+    candidate: PathBuf,
+    extensions: &[String],
+    options: &ResolveOptions,
+    path_aliases: Option<&PathAliases>,
+) -> Vec<PathBuf> {
+    // A target platform's suffixes replace the tsconfig list
+    let mut suffixes: Vec<&str> = match &options.platform_suffixes {
+        Some(suffixes) => suffixes.as_slice().iter().map(String::as_str).collect(),
+        None => path_aliases
+            .map(|a| a.module_suffixes.iter().map(String::as_str).collect())
+            .unwrap_or_default(),
+    };
+    if suffixes.is_empty() {
+        suffixes.push("");
+    }
+    let source = path_aliases.and_then(|a| a.source_for_output(&candidate));
+
+    let mut resolved = Vec::new();
+    for suffix in suffixes {
+        let found = source
+            .as_ref()
+            .and_then(|source| check_candidates(source, extensions, suffix))
+            .or_else(|| check_candidates(&candidate, extensions, suffix));
+        if let Some(found) = found {
+            if options.module_suffixes == ModuleSuffixes::First {
+                return vec![found];
+            }
+            if !resolved.contains(&found) {
+                resolved.push(found);
+            }
+        }
+    }
+    resolved
+}
+
+/// Checks various possibilities for the import path, with a `moduleSuffixes`
+/// entry (e.g. `.ios`) inserted before the extension.
+/// Returns the resolved, canonicalized PathBuf if found.
+fn check_candidates(candidate: &Path, extensions: &[String], suffix: &str) -> Option<PathBuf> {
+    if suffix.is_empty() {
+        if let Some(canonical) = handle_if_file(candidate) {
+            return Some(canonical);
+        }
+    }
+
+    // TypeScript maps `./foo.js` to `./foo.ts`, `./foo.mjs` to `./foo.mts`, etc.
+    if let Some(js_ext) = candidate.extension().and_then(|e| e.to_str()) {
+        let stem = candidate.with_extension("");
+        if !suffix.is_empty() {
+            let file_name = format!(
+                "{}{}.{}",
+                stem.file_name().unwrap_or_default().to_string_lossy(),
+                suffix,
+                js_ext
+            );
+            if let Some(canonical) = handle_if_file(&stem.with_file_name(file_name)) {
+                return Some(canonical);
+            }
+        }
+        for ts_ext in ts_extensions_for_js(js_ext) {
+            let file_name = format!(
+                "{}{}{}",
+                stem.file_name().unwrap_or_default().to_string_lossy(),
+                suffix,
+                ts_ext
+            );
+            if let Some(canonical) = handle_if_file(&stem.with_file_name(file_name)) {
+                return Some(canonical);
+            }
+        }
+    }
+
+    for ext in extensions {
+        let file_name = candidate
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned();
+
+        let new_file_name = format!("{}{}{}", file_name, suffix, ext);
+        let new_path = candidate.with_file_name(&new_file_name);
+
+        if let Some(canonical) = handle_if_file(&new_path) {
+            return Some(canonical);
+        }
+    }
+
+    // If candidate is a directory, try index files
+    if candidate.is_dir() {
+        for idx_ext in EXTENSIONS {
+            let idx_file = candidate.join(format!("index{}{}", suffix, idx_ext));
+            if let Some(canonical) = handle_if_file(&idx_file) {
+                return Some(canonical);
+            }
+        }
+    }
+
+    debug!(
+        "check_candidates: Could not resolve candidate {:?}",
+        candidate
+    );
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Maps `generated:<name>` to `<name>.ts` next to the importer.
+    #[derive(Debug)]
+    struct GeneratedResolver;
+
+    impl Resolver for GeneratedResolver {
+        fn name(&self) -> &'static str {
+            "generated"
+        }
+
+        fn resolve(&self, ctx: &ResolveContext, request: &ResolveRequest) -> Vec<PathBuf> {
+            let Some(name) = request.specifier.strip_prefix("generated:") else {
+                return Vec::new();
+            };
+            let candidate = request.importer.with_file_name(name);
+            ctx.probe(request, candidate)
+        }
+    }
+
+    fn setup() -> (TempDir, PathBuf) {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().canonicalize().unwrap();
+        fs::write(dir.join("a.ts"), "").unwrap();
+        fs::write(dir.join("schema.ts"), "").unwrap();
+        (temp, dir)
+    }
+
+    #[test]
+    fn test_custom_resolver_in_chain() {
+        let (_temp, dir) = setup();
+        let mut options = ResolveOptions::default();
+        options.resolvers.push(Arc::new(GeneratedResolver));
+        let ctx = ResolveContext::new(&options, None);
+        let importer = dir.join("a.ts");
+
+        assert_eq!(
+            ctx.resolve(&ResolveRequest::new(&importer, "generated:schema", None)),
+            Some(Resolved {
+                paths: vec![dir.join("schema.ts")],
+                resolver: "generated",
+            })
+        );
+        assert_eq!(
+            ctx.resolve(&ResolveRequest::new(&importer, "./schema", None))
+                .map(|resolved| resolved.resolver),
+            Some("relative")
+        );
+    }
+
+    #[test]
+    fn test_resolvers_left_out_are_disabled() {
+        let (_temp, dir) = setup();
+        let options = ResolveOptions {
+            aliases: AliasMap::from_config(
+                &BTreeMap::from([("@schema".to_string(), "./schema.ts".to_string())]),
+                &dir,
+            ),
+            resolvers: resolvers_from_names(&["relative", "unknown"]),
+            ..Default::default()
+        };
+        assert_eq!(options.resolvers.len(), 1);
+        let ctx = ResolveContext::new(&options, None);
+        let importer = dir.join("a.ts");
+
+        assert_eq!(
+            ctx.resolve(&ResolveRequest::new(&importer, "@schema", None)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_alias_cycle_terminates() {
+        let (_temp, dir) = setup();
+        let options = ResolveOptions {
+            aliases: AliasMap::from_config(
+                &BTreeMap::from([
+                    ("left".to_string(), "right".to_string()),
+                    ("right".to_string(), "left".to_string()),
+                ]),
+                &dir,
+            ),
+            ..Default::default()
+        };
+        let ctx = ResolveContext::new(&options, None);
+        let importer = dir.join("a.ts");
+
+        assert_eq!(
+            ctx.resolve(&ResolveRequest::new(&importer, "left", None)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_strip_query() {
+        assert_eq!(strip_query("./logo.svg?url"), "./logo.svg");
+        assert_eq!(strip_query("./notes.md?raw&lang=en"), "./notes.md");
+        assert_eq!(strip_query("./icons.svg#close"), "./icons.svg");
+        assert_eq!(strip_query("#internal/db?inline"), "#internal/db");
+        assert_eq!(strip_query("./utils"), "./utils");
+    }
+
+    #[test]
+    fn test_classify_unresolved() {
+        let (_temp, dir) = setup();
+        let options = ResolveOptions {
+            aliases: AliasMap::from_config(
+                &BTreeMap::from([("@".to_string(), "./src".to_string())]),
+                &dir,
+            ),
+            ..Default::default()
+        };
+        let ctx = ResolveContext::new(&options, None);
+        let importer = dir.join("a.ts");
+        let classify =
+            |specifier| ctx.classify_unresolved(&ResolveRequest::new(&importer, specifier, None));
+
+        assert_eq!(classify("./UserServce"), Some(UnresolvedKind::Relative));
+        assert_eq!(classify("@/missing"), Some(UnresolvedKind::Alias));
+        assert_eq!(classify("#internal/db"), Some(UnresolvedKind::Alias));
+        assert_eq!(classify("react"), None);
+        assert_eq!(classify("fs"), None);
+        assert_eq!(classify("node:fs"), None);
+        assert_eq!(classify("/logo.svg"), None);
+
+        // Without the relative resolver, relative imports are left alone on purpose
+        let options = ResolveOptions {
+            resolvers: resolvers_from_names(&["tsconfig"]),
+            ..Default::default()
+        };
+        let ctx = ResolveContext::new(&options, None);
+        assert_eq!(
+            ctx.classify_unresolved(&ResolveRequest::new(&importer, "./UserServce", None)),
+            None
+        );
+    }
+}
@@ -0,0 +1,224 @@
+//! Script extraction for single-file components.
+//!
+//! Vue, Svelte and Astro files embed their JavaScript/TypeScript inside
+//! `<script>` tags (or, for Astro, a `---` frontmatter fence). This module
+//! pulls those blocks out so they can be parsed like regular modules, and
+//! records where each block starts so line numbers map back to the original file.
+
+use regex::Regex;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// The kind of single-file component, based on file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SfcKind {
+    Vue,
+    Svelte,
+    Astro,
+}
+
+impl SfcKind {
+    /// Returns the component kind for a path, or None for plain script files.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension().and_then(|e| e.to_str())? {
+            "vue" => Some(SfcKind::Vue),
+            "svelte" => Some(SfcKind::Svelte),
+            "astro" => Some(SfcKind::Astro),
+            _ => None,
+        }
+    }
+}
+
+/// A script block extracted from a single-file component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptBlock {
+    /// The script source code
+    pub content: String,
+    /// The `lang` attribute (e.g., "ts"), if any
+    pub lang: Option<String>,
+    /// Number of lines preceding the block in the original file
+    pub line_offset: u32,
+}
+
+impl ScriptBlock {
+    /// Whether this block should be parsed as TypeScript.
+    pub fn is_typescript(&self) -> bool {
+        matches!(self.lang.as_deref(), Some("ts" | "tsx" | "typescript"))
+    }
+
+    /// Whether this block may contain JSX.
+    pub fn is_jsx(&self) -> bool {
+        matches!(self.lang.as_deref(), Some("tsx" | "jsx"))
+    }
+}
+
+fn script_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?is)<script\b([^>]*)>(.*?)</script\s*>").expect("valid script regex")
+    })
+}
+
+fn attr_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#"(?i)\b(lang|type|src)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s>]+))"#)
+            .expect("valid attribute regex")
+    })
+}
+
+/// Extracts all script blocks from a single-file component.
+///
+/// - Vue: `<script>` and `<script setup>`
+/// - Svelte: `<script>` and `<script context="module">`
+/// - Astro: the `---` frontmatter (always TypeScript) plus any `<script>` tags
+///
+/// Blocks with a `src` attribute or a non-JavaScript `type` (e.g. JSON-LD) are skipped.
+pub fn extract_script_blocks(source: &str, kind: SfcKind) -> Vec<ScriptBlock> {
+    let mut blocks = Vec::new();
+    let mut template_start = 0;
+
+    if kind == SfcKind::Astro {
+        if let Some((block, end)) = extract_frontmatter(source) {
+            blocks.push(block);
+            template_start = end;
+        }
+    }
+
+    for caps in script_regex().captures_iter(&source[template_start..]) {
+        let attrs = caps.get(1).map_or("", |m| m.as_str());
+        let content = match caps.get(2) {
+            Some(m) => m,
+            None => continue,
+        };
+
+        let mut lang = None;
+        let mut skip = false;
+        for attr in attr_regex().captures_iter(attrs) {
+            let name = attr[1].to_lowercase();
+            let value = attr
+                .get(2)
+                .or_else(|| attr.get(3))
+                .or_else(|| attr.get(4))
+                .map_or("", |m| m.as_str())
+                .to_lowercase();
+            match name.as_str() {
+                "lang" => lang = Some(value),
+                "src" => skip = true,
+                "type" => skip |= !is_script_type(&value),
+                _ => {}
+            }
+        }
+        if skip {
+            continue;
+        }
+
+        // Astro processes <script> tags as TypeScript by default
+        if lang.is_none() && kind == SfcKind::Astro {
+            lang = Some("ts".to_string());
+        }
+
+        let start = template_start + content.start();
+        blocks.push(ScriptBlock {
+            content: content.as_str().to_string(),
+            lang,
+            line_offset: count_lines(&source[..start]),
+        });
+    }
+
+    blocks
+}
+
+/// Extracts Astro frontmatter, returning the block and the byte offset where it ends.
+fn extract_frontmatter(source: &str) -> Option<(ScriptBlock, usize)> {
+    let trimmed = source.trim_start();
+    let rest = trimmed.strip_prefix("---")?;
+    let leading = source.len() - trimmed.len();
+    let body_start = leading + 3 + rest.find('\n')? + 1;
+
+    let mut offset = body_start;
+    for line in source[body_start..].split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            let block = ScriptBlock {
+                content: source[body_start..offset].to_string(),
+                lang: Some("ts".to_string()),
+                line_offset: count_lines(&source[..body_start]),
+            };
+            return Some((block, offset + line.len()));
+        }
+        offset += line.len();
+    }
+
+    None
+}
+
+fn is_script_type(value: &str) -> bool {
+    matches!(
+        value,
+        "" | "module" | "text/javascript" | "application/javascript" | "text/typescript"
+    )
+}
+
+fn count_lines(text: &str) -> u32 {
+    text.bytes().filter(|&b| b == b'\n').count() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vue_script_and_setup_blocks() {
+        let source = r#"<template>
+  <div />
+</template>
+
+<script>
+import A from './A.vue';
+</script>
+
+<script setup lang="ts">
+import { b } from './b';
+</script>
+"#;
+        let blocks = extract_script_blocks(source, SfcKind::Vue);
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].lang, None);
+        assert_eq!(blocks[0].line_offset, 4);
+        assert!(blocks[0].content.contains("./A.vue"));
+        assert_eq!(blocks[1].lang.as_deref(), Some("ts"));
+        assert!(blocks[1].is_typescript());
+        assert_eq!(blocks[1].line_offset, 8);
+    }
+
+    #[test]
+    fn test_svelte_skips_non_script_types() {
+        let source = r#"<script context="module" lang='ts'>
+import { x } from './x';
+</script>
+<script type="application/ld+json">{"@context": "https://schema.org"}</script>
+<script src="./external.js"></script>
+"#;
+        let blocks = extract_script_blocks(source, SfcKind::Svelte);
+
+        assert_eq!(blocks.len(), 1);
+        assert!(blocks[0].is_typescript());
+        assert_eq!(blocks[0].line_offset, 0);
+    }
+
+    #[test]
+    fn test_astro_frontmatter() {
+        let source = "---\nimport Layout from '../layouts/Layout.astro';\n---\n<Layout />\n<script>\nimport './client';\n</script>\n";
+        let blocks = extract_script_blocks(source, SfcKind::Astro);
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(
+            blocks[0].content,
+            "import Layout from '../layouts/Layout.astro';\n"
+        );
+        assert_eq!(blocks[0].line_offset, 1);
+        assert!(blocks[1].is_typescript());
+        assert_eq!(blocks[1].line_offset, 4);
+    }
+}
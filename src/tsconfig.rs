@@ -1,14 +1,18 @@
+use crate::filesystem::NearestAncestorCache;
+use crate::utils::{clean_path, split_package_name};
 use log::debug;
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Parsed tsconfig.json structure (only the parts we need).
 #[derive(Debug, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 struct TsConfigJson {
-    extends: Option<String>,
+    extends: Option<Extends>,
     compiler_options: Option<CompilerOptions>,
+    references: Option<Vec<ProjectReference>>,
 }
 
 #[derive(Debug, Deserialize, Default)]
@@ -16,8 +20,38 @@ struct TsConfigJson {
 struct CompilerOptions {
     base_url: Option<String>,
     paths: Option<HashMap<String, Vec<String>>>,
+    root_dir: Option<String>,
+    out_dir: Option<String>,
+    root_dirs: Option<Vec<String>>,
+    module_suffixes: Option<Vec<String>>,
 }
 
+#[derive(Debug, Deserialize)]
+struct ProjectReference {
+    path: String,
+}
+
+/// `extends` is a single config or, since TypeScript 5.0, a list applied in order.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Extends {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Extends {
+    fn as_slice(&self) -> &[String] {
+        match self {
+            Extends::One(one) => std::slice::from_ref(one),
+            Extends::Many(many) => many,
+        }
+    }
+}
+
+/// Placeholder (TypeScript 5.5) for the directory of the config being loaded,
+/// which lets shared base configs declare project-relative paths.
+const CONFIG_DIR_TEMPLATE: &str = "${configDir}";
+
 /// Resolved path alias configuration.
 #[derive(Debug, Clone)]
 pub struct PathAliases {
@@ -28,54 +62,226 @@ pub struct PathAliases {
     pub paths: HashMap<String, Vec<PathBuf>>,
     /// Directory containing the tsconfig.json.
     pub config_dir: PathBuf,
+    /// Directory `paths` replacements are relative to when there's no `baseUrl`
+    /// (the directory of the config that declares `paths`).
+    pub paths_dir: PathBuf,
+    /// `compilerOptions.rootDir` (defaults to the config directory).
+    pub root_dir: Option<PathBuf>,
+    /// `compilerOptions.outDir`, where compiled output and declarations are emitted.
+    pub out_dir: Option<PathBuf>,
+    /// `compilerOptions.rootDirs`: directories merged into one virtual directory
+    /// for relative imports.
+    pub root_dirs: Vec<PathBuf>,
+    /// `compilerOptions.moduleSuffixes` (e.g. `.ios`, `.native`, `""`), tried in order.
+    pub module_suffixes: Vec<String>,
+    /// Projects listed in `references`, loaded with their own settings.
+    pub references: Vec<PathAliases>,
 }
 
 impl PathAliases {
-    /// Attempts to resolve an import path using the configured aliases.
-    /// Returns the resolved path if successful, None otherwise.
-    pub fn resolve(&self, import: &str) -> Option<PathBuf> {
-        // Try exact match first
-        if let Some(replacements) = self.paths.get(import) {
-            for replacement in replacements {
-                let resolved = self.config_dir.join(replacement);
-                if resolved.exists() || resolved.with_extension("ts").exists() {
-                    debug!("Resolved '{}' via exact match to {:?}", import, resolved);
-                    return Some(resolved);
+    fn new(config_dir: &Path) -> Self {
+        Self {
+            base_url: None,
+            paths: HashMap::new(),
+            config_dir: config_dir.to_path_buf(),
+            paths_dir: config_dir.to_path_buf(),
+            root_dir: None,
+            out_dir: None,
+            root_dirs: Vec::new(),
+            module_suffixes: Vec::new(),
+            references: Vec::new(),
+        }
+    }
+
+    /// Takes every option set by a base config, overriding what's already here.
+    fn inherit(&mut self, base: PathAliases) {
+        if base.base_url.is_some() {
+            self.base_url = base.base_url;
+        }
+        if !base.paths.is_empty() {
+            self.paths = base.paths;
+            self.paths_dir = base.paths_dir;
+        }
+        if base.root_dir.is_some() {
+            self.root_dir = base.root_dir;
+        }
+        if base.out_dir.is_some() {
+            self.out_dir = base.out_dir;
+        }
+        if !base.root_dirs.is_empty() {
+            self.root_dirs = base.root_dirs;
+        }
+        if !base.module_suffixes.is_empty() {
+            self.module_suffixes = base.module_suffixes;
+        }
+    }
+
+    /// This project followed by every project it references (transitively).
+    pub fn projects(&self) -> Vec<&PathAliases> {
+        let mut projects = vec![self];
+        let mut i = 0;
+        while i < projects.len() {
+            let project = projects[i];
+            for reference in &project.references {
+                if !projects
+                    .iter()
+                    .any(|p| p.config_dir == reference.config_dir)
+                {
+                    projects.push(reference);
                 }
             }
+            i += 1;
         }
+        projects
+    }
 
-        // Try wildcard patterns (e.g., "@/*" -> "src/*")
-        for (pattern, replacements) in &self.paths {
-            if let Some(prefix) = pattern.strip_suffix('*') {
-                if let Some(suffix) = import.strip_prefix(prefix) {
-                    for replacement in replacements {
-                        let replacement_str = replacement.to_string_lossy();
-                        if let Some(base) = replacement_str.strip_suffix('*') {
-                            let resolved = self.config_dir.join(base).join(suffix);
-                            debug!("Trying wildcard resolution: '{}' -> {:?}", import, resolved);
-                            // Return the path even if it doesn't exist yet -
-                            // the caller will handle extension resolution
-                            return Some(resolved);
-                        }
+    /// Maps a path inside the `outDir` of this project (or a referenced one) back to
+    /// the corresponding source path under its `rootDir`, like `tsc --build` does.
+    /// Declaration extensions are dropped so the source extension can be probed.
+    pub fn source_for_output(&self, path: &Path) -> Option<PathBuf> {
+        let path = clean_path(path);
+        self.projects().into_iter().find_map(|project| {
+            let out_dir = project.out_dir.as_ref()?;
+            let relative = path.strip_prefix(out_dir).ok()?;
+            let root_dir = project.root_dir.as_ref().unwrap_or(&project.config_dir);
+
+            let mut source = root_dir.join(relative);
+            if let Some(name) = source.file_name().and_then(|n| n.to_str()) {
+                for ext in [".d.ts", ".d.mts", ".d.cts"] {
+                    if let Some(stem) = name.strip_suffix(ext) {
+                        source = source.with_file_name(stem);
+                        break;
                     }
                 }
             }
+            debug!("Mapped output {:?} to source {:?}", path, source);
+            Some(source)
+        })
+    }
+
+    /// For a relative import that lands inside one of the `rootDirs`, returns the
+    /// same relative path inside each of the other root dirs, in order. The most
+    /// specific root dir containing the path is used, like `tsc`.
+    pub fn root_dirs_candidates(&self, path: &Path) -> Vec<PathBuf> {
+        let Some((matched, relative)) = self
+            .root_dirs
+            .iter()
+            .filter_map(|dir| Some((dir, path.strip_prefix(dir).ok()?)))
+            .max_by_key(|(dir, _)| dir.components().count())
+        else {
+            return Vec::new();
+        };
+
+        self.root_dirs
+            .iter()
+            .filter(|dir| *dir != matched)
+            .map(|dir| dir.join(relative))
+            .collect()
+    }
+
+    /// Returns the paths to try for an import, in the order `tsc` tries them:
+    /// an exact `paths` entry, otherwise every replacement of the wildcard pattern
+    /// with the longest matching prefix, and finally `baseUrl`.
+    ///
+    /// Candidates may not exist; the caller probes extensions and moves on to the
+    /// next candidate when nothing resolves.
+    pub fn resolve_candidates(&self, import: &str) -> Vec<PathBuf> {
+        // Replacements are relative to baseUrl if set, otherwise to the declaring tsconfig
+        let paths_base = self.base_url.as_ref().unwrap_or(&self.paths_dir);
+        let mut candidates = Vec::new();
+
+        if let Some(replacements) = self.paths.get(import).filter(|_| !import.contains('*')) {
+            debug!("Matched '{}' exactly in paths", import);
+            candidates.extend(replacements.iter().map(|r| paths_base.join(r)));
+        } else if let Some((pattern, matched)) = self.best_pattern_match(import) {
+            debug!("Matched '{}' against paths pattern '{}'", import, pattern);
+            for replacement in &self.paths[pattern] {
+                let replacement = replacement.to_string_lossy().replacen('*', matched, 1);
+                candidates.push(paths_base.join(replacement));
+            }
         }
 
-        // Try baseUrl resolution
         if let Some(base_url) = &self.base_url {
-            let resolved = base_url.join(import);
-            debug!("Trying baseUrl resolution: '{}' -> {:?}", import, resolved);
-            return Some(resolved);
+            candidates.push(base_url.join(import));
         }
 
-        None
+        candidates
+    }
+
+    /// Whether a `paths` entry matches the import (`baseUrl` alone doesn't count).
+    pub fn matches_paths(&self, import: &str) -> bool {
+        self.paths.contains_key(import) || self.best_pattern_match(import).is_some()
+    }
+
+    /// Finds the wildcard pattern with the longest prefix matching the import,
+    /// returning it with the text matched by `*`. Ties are broken by the longer
+    /// suffix, then alphabetically, so the result never depends on map order.
+    fn best_pattern_match<'a>(&'a self, import: &'a str) -> Option<(&'a str, &'a str)> {
+        self.paths
+            .keys()
+            .filter_map(|pattern| {
+                let (prefix, suffix) = pattern.split_once('*')?;
+                let matched = import.strip_prefix(prefix)?.strip_suffix(suffix)?;
+                Some((pattern.as_str(), prefix.len(), suffix.len(), matched))
+            })
+            .max_by(|a, b| (a.1, a.2).cmp(&(b.1, b.2)).then_with(|| b.0.cmp(a.0)))
+            .map(|(pattern, _, _, matched)| (pattern, matched))
     }
 }
 
-/// Loads and parses a tsconfig.json file, following `extends` chains.
+/// Finds the tsconfig that applies to each source file.
+///
+/// Either a single explicit config is used for every file (`--tsconfig`), or the
+/// closest `tsconfig.json` above each file is loaded, like `tsc` does. Parsed
+/// configs are cached per directory, so each one is only read once.
+#[derive(Debug, Default)]
+pub struct TsConfigCache {
+    /// Config applied to every file, if one was given explicitly
+    explicit: Option<Arc<PathAliases>>,
+    /// Directory -> nearest config at or above it (None if it fails to load)
+    nearest: NearestAncestorCache<Option<Arc<PathAliases>>>,
+}
+
+impl TsConfigCache {
+    /// Uses the same config for every file.
+    pub fn explicit(aliases: PathAliases) -> Self {
+        Self {
+            explicit: Some(Arc::new(aliases)),
+            ..Default::default()
+        }
+    }
+
+    /// Uses the nearest `tsconfig.json` above each file.
+    pub fn nearest() -> Self {
+        Self::default()
+    }
+
+    /// Returns the config that owns a source file, if any.
+    pub fn for_file(&self, file: &Path) -> Option<Arc<PathAliases>> {
+        if let Some(aliases) = &self.explicit {
+            return Some(Arc::clone(aliases));
+        }
+
+        // The nearest tsconfig.json wins even if it can't be loaded, like `tsc`
+        self.nearest
+            .get(file, |dir| {
+                let candidate = dir.join("tsconfig.json");
+                candidate.is_file().then(|| {
+                    debug!("Found tsconfig for {:?} at {:?}", file, candidate);
+                    load_tsconfig(&candidate).map(Arc::new)
+                })
+            })
+            .flatten()
+    }
+}
+
+/// Loads and parses a tsconfig.json file, following `extends` chains
+/// and project `references`.
 pub fn load_tsconfig(path: &Path) -> Option<PathAliases> {
+    load_project(path, &mut HashSet::new())
+}
+
+fn load_project(path: &Path, visited: &mut HashSet<PathBuf>) -> Option<PathAliases> {
     let config_path = if path.is_file() {
         path.to_path_buf()
     } else {
@@ -86,87 +292,539 @@ pub fn load_tsconfig(path: &Path) -> Option<PathAliases> {
         debug!("tsconfig not found at {:?}", config_path);
         return None;
     }
+    if !visited.insert(clean_path(&config_path)) {
+        return None;
+    }
 
-    let config_dir = config_path.parent().unwrap_or(Path::new(".")).to_path_buf();
+    // Absolute, so output paths can be matched against `outDir`
+    let config_dir = config_path.parent().unwrap_or(Path::new("."));
+    let config_dir = config_dir
+        .canonicalize()
+        .unwrap_or_else(|_| config_dir.to_path_buf());
+    let (mut aliases, references) =
+        load_tsconfig_with_extends(&config_path, &config_dir, &config_dir, &mut HashSet::new())?;
+
+    for reference in references {
+        match load_project(&config_dir.join(&reference.path), visited) {
+            Some(project) => aliases.references.push(project),
+            None => debug!(
+                "Could not load project reference '{}' from {:?}",
+                reference.path, config_path
+            ),
+        }
+    }
 
-    load_tsconfig_with_extends(&config_path, &config_dir)
+    Some(aliases)
 }
 
-fn load_tsconfig_with_extends(path: &Path, config_dir: &Path) -> Option<PathAliases> {
+/// Loads a config and its `extends` chain. The config's own `references` are
+/// returned separately, since (unlike compiler options) they aren't inherited.
+///
+/// `leaf_dir` is the directory of the config the chain started from, which
+/// `${configDir}` expands to in every config of the chain.
+fn load_tsconfig_with_extends(
+    path: &Path,
+    config_dir: &Path,
+    leaf_dir: &Path,
+    chain: &mut HashSet<PathBuf>,
+) -> Option<(PathAliases, Vec<ProjectReference>)> {
     let content = std::fs::read_to_string(path).ok()?;
-    let config: TsConfigJson = serde_json::from_str(&content)
+    let config: TsConfigJson = serde_json::from_str(&strip_jsonc(&content))
         .map_err(|e| {
             log::warn!("Failed to parse tsconfig '{}': {}", path.display(), e);
             e
         })
         .ok()?;
+    let mut aliases = PathAliases::new(config_dir);
 
-    // Start with parent config if extends is specified
-    let mut aliases = if let Some(extends) = &config.extends {
-        let parent_path = resolve_extends(extends, config_dir)?;
-        let parent_dir = parent_path.parent().unwrap_or(Path::new(".")).to_path_buf();
-        load_tsconfig_with_extends(&parent_path, &parent_dir).unwrap_or_else(|| PathAliases {
-            base_url: None,
-            paths: HashMap::new(),
-            config_dir: config_dir.to_path_buf(),
-        })
-    } else {
-        PathAliases {
-            base_url: None,
-            paths: HashMap::new(),
-            config_dir: config_dir.to_path_buf(),
+    // Apply each base config in order; later ones override earlier ones
+    chain.insert(clean_path(path));
+    for extends in config.extends.iter().flat_map(Extends::as_slice) {
+        let Some(parent_path) = resolve_extends(extends, config_dir) else {
+            log::warn!(
+                "Could not resolve extends '{}' in '{}'",
+                extends,
+                path.display()
+            );
+            continue;
+        };
+        if chain.contains(&clean_path(&parent_path)) {
+            log::warn!("Circular extends '{}' in '{}'", extends, path.display());
+            continue;
         }
-    };
+        let parent_dir = parent_path.parent().unwrap_or(Path::new("."));
+        if let Some((parent, _)) =
+            load_tsconfig_with_extends(&parent_path, parent_dir, leaf_dir, chain)
+        {
+            aliases.inherit(parent);
+        }
+    }
+    chain.remove(&clean_path(path));
 
     // Override with current config's values
-    aliases.config_dir = config_dir.to_path_buf();
-
     if let Some(compiler_options) = config.compiler_options {
         if let Some(base_url) = compiler_options.base_url {
-            aliases.base_url = Some(config_dir.join(&base_url));
+            aliases.base_url = Some(config_relative(&base_url, config_dir, leaf_dir));
         }
 
+        // `paths` replaces any inherited mapping as a whole
         if let Some(paths) = compiler_options.paths {
-            for (pattern, replacements) in paths {
-                let resolved_replacements: Vec<PathBuf> =
-                    replacements.into_iter().map(PathBuf::from).collect();
-                aliases.paths.insert(pattern, resolved_replacements);
-            }
+            aliases.paths = paths
+                .into_iter()
+                .map(|(pattern, replacements)| {
+                    let replacements = replacements
+                        .iter()
+                        .map(|r| PathBuf::from(expand_config_dir(r, leaf_dir)))
+                        .collect();
+                    (pattern, replacements)
+                })
+                .collect();
+            aliases.paths_dir = config_dir.to_path_buf();
+        }
+
+        // Directories are relative to the config that declares them
+        if let Some(root_dir) = compiler_options.root_dir {
+            aliases.root_dir = Some(clean_path(&config_relative(
+                &root_dir, config_dir, leaf_dir,
+            )));
+        }
+        if let Some(out_dir) = compiler_options.out_dir {
+            aliases.out_dir = Some(clean_path(&config_relative(&out_dir, config_dir, leaf_dir)));
+        }
+        if let Some(root_dirs) = compiler_options.root_dirs {
+            aliases.root_dirs = root_dirs
+                .iter()
+                .map(|dir| clean_path(&config_relative(dir, config_dir, leaf_dir)))
+                .collect();
+        }
+        if let Some(module_suffixes) = compiler_options.module_suffixes {
+            aliases.module_suffixes = module_suffixes;
         }
     }
 
     debug!("Loaded tsconfig from {:?}: {:?}", path, aliases);
-    Some(aliases)
+    Some((aliases, config.references.unwrap_or_default()))
+}
+
+/// Resolves a path option relative to the config that declares it.
+fn config_relative(value: &str, config_dir: &Path, leaf_dir: &Path) -> PathBuf {
+    config_dir.join(expand_config_dir(value, leaf_dir))
+}
+
+/// Substitutes the `${configDir}` placeholder with the leaf config's directory.
+fn expand_config_dir(value: &str, leaf_dir: &Path) -> String {
+    value.replace(CONFIG_DIR_TEMPLATE, &leaf_dir.to_string_lossy())
 }
 
+/// Resolves an `extends` entry to a config file path.
+///
+/// Relative entries are resolved against the config directory (adding `.json`
+/// if needed). Anything else is a package, looked up in `node_modules` of the
+/// config directory and each parent directory, like Node does.
 fn resolve_extends(extends: &str, config_dir: &Path) -> Option<PathBuf> {
-    if extends.starts_with('.') {
-        // Relative path
-        let mut path = config_dir.join(extends);
-        if !path.extension().map_or(false, |e| e == "json") {
-            path = path.with_extension("json");
+    if extends.starts_with("./") || extends.starts_with("../") || Path::new(extends).is_absolute() {
+        return resolve_config_file(&config_dir.join(extends));
+    }
+
+    let (package, subpath) = split_package_name(extends);
+    for dir in config_dir.ancestors() {
+        let package_dir = dir.join("node_modules").join(package);
+        if !package_dir.is_dir() {
+            continue;
         }
-        if path.exists() {
-            return Some(path);
+        if let Some(resolved) = resolve_package_config(&package_dir, subpath) {
+            return Some(resolved);
+        }
+    }
+
+    debug!("Could not resolve tsconfig extends: {}", extends);
+    None
+}
+
+/// Finds a config inside a package: its `exports`, then the `tsconfig` field
+/// of package.json, then `tsconfig.json` (or the subpath) in the package root.
+fn resolve_package_config(package_dir: &Path, subpath: &str) -> Option<PathBuf> {
+    let manifest: Option<serde_json::Value> =
+        std::fs::read_to_string(package_dir.join("package.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok());
+
+    if let Some(manifest) = &manifest {
+        let key = if subpath.is_empty() {
+            ".".to_string()
+        } else {
+            format!("./{}", subpath)
+        };
+        let exports = manifest.get("exports");
+        let target = exports
+            .and_then(|e| e.get(&key).or_else(|| e.get(format!("{}.json", key))))
+            .or(exports.filter(|e| subpath.is_empty() && !is_subpath_map(e)))
+            .and_then(export_target);
+        if let Some(target) = target {
+            return resolve_config_file(&package_dir.join(target));
         }
-        // Try without adding extension (it might be a directory with tsconfig.json)
-        let dir_path = config_dir.join(extends).join("tsconfig.json");
-        if dir_path.exists() {
-            return Some(dir_path);
+
+        if subpath.is_empty() {
+            if let Some(field) = manifest.get("tsconfig").and_then(|t| t.as_str()) {
+                return resolve_config_file(&package_dir.join(field));
+            }
         }
+    }
+
+    if subpath.is_empty() {
+        resolve_config_file(&package_dir.join("tsconfig.json"))
     } else {
-        // Node module - look in node_modules
-        let node_modules_path = config_dir.join("node_modules").join(extends);
-        if node_modules_path.exists() {
-            return Some(node_modules_path);
+        resolve_config_file(&package_dir.join(subpath))
+    }
+}
+
+/// Whether an `exports` object maps subpaths (`"./base": ...`) rather than conditions.
+fn is_subpath_map(exports: &serde_json::Value) -> bool {
+    exports
+        .as_object()
+        .is_some_and(|map| map.keys().any(|k| k.starts_with('.')))
+}
+
+/// Picks the file from an `exports` value, following the conditions `tsc` uses for configs.
+fn export_target(value: &serde_json::Value) -> Option<&str> {
+    match value {
+        serde_json::Value::String(target) => Some(target),
+        serde_json::Value::Object(conditions) => ["types", "require", "import", "default"]
+            .iter()
+            .find_map(|condition| conditions.get(*condition).and_then(export_target)),
+        _ => None,
+    }
+}
+
+/// Returns the config file at `path`, trying `path.json` and `path/tsconfig.json` too.
+fn resolve_config_file(path: &Path) -> Option<PathBuf> {
+    if path.is_file() {
+        return Some(path.to_path_buf());
+    }
+    if path.extension().is_none_or(|e| e != "json") {
+        let mut with_json = path.as_os_str().to_owned();
+        with_json.push(".json");
+        let with_json = PathBuf::from(with_json);
+        if with_json.is_file() {
+            return Some(with_json);
+        }
+    }
+    let in_dir = path.join("tsconfig.json");
+    in_dir.is_file().then_some(in_dir)
+}
+
+/// Converts JSONC (as accepted by `tsc`) to plain JSON by removing comments
+/// and trailing commas. String contents are left untouched.
+pub fn strip_jsonc(content: &str) -> String {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut out = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
         }
-        // Try with tsconfig.json
-        let with_tsconfig = node_modules_path.join("tsconfig.json");
-        if with_tsconfig.exists() {
-            return Some(with_tsconfig);
+
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            '/' if chars.peek() == Some(&'/') => while chars.next_if(|&c| c != '\n').is_some() {},
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    // Keep line breaks so serde's error positions stay meaningful
+                    if c == '\n' {
+                        out.push(c);
+                    }
+                    prev = c;
+                }
+            }
+            _ => out.push(c),
         }
     }
 
-    debug!("Could not resolve tsconfig extends: {}", extends);
-    None
+    remove_trailing_commas(&out)
+}
+
+/// Removes commas that are directly followed (ignoring whitespace) by `}` or `]`.
+fn remove_trailing_commas(json: &str) -> String {
+    let mut out = String::with_capacity(json.len());
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, c) in json.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if c == ',' {
+            let next = json[i + 1..].trim_start().chars().next();
+            if matches!(next, Some('}' | ']')) {
+                continue;
+            }
+        }
+        out.push(c);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_strip_jsonc() {
+        let content = r#"{
+  // Line comment
+  "compilerOptions": {
+    /* Block
+       comment */
+    "baseUrl": ".", // trailing
+    "paths": { "@/*": ["src/*",], },
+    "outDir": "dist//*not a comment*/",
+  },
+}"#;
+        let json: serde_json::Value = serde_json::from_str(&strip_jsonc(content)).unwrap();
+
+        assert_eq!(json["compilerOptions"]["baseUrl"], ".");
+        assert_eq!(json["compilerOptions"]["paths"]["@/*"][0], "src/*");
+        assert_eq!(json["compilerOptions"]["outDir"], "dist//*not a comment*/");
+    }
+
+    #[test]
+    fn test_load_jsonc_tsconfig_with_extends() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("tsconfig.base.json"),
+            "{\n  // shared\n  \"compilerOptions\": { \"paths\": { \"@/*\": [\"src/*\"], }, },\n}",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("tsconfig.json"),
+            "/* app */ { \"extends\": \"./tsconfig.base.json\", }",
+        )
+        .unwrap();
+
+        let aliases = load_tsconfig(dir.path()).unwrap();
+        assert_eq!(aliases.paths["@/*"], vec![PathBuf::from("src/*")]);
+    }
+
+    #[test]
+    fn test_nearest_tsconfig_is_cached_per_directory() {
+        let dir = TempDir::new().unwrap();
+        let package = dir.path().join("packages/web");
+        std::fs::create_dir_all(package.join("src/deep")).unwrap();
+        std::fs::write(
+            package.join("tsconfig.json"),
+            r#"{ "compilerOptions": { "paths": { "@/*": ["src/*"] } } }"#,
+        )
+        .unwrap();
+
+        let cache = TsConfigCache::nearest();
+        let first = cache.for_file(&package.join("src/deep/a.ts")).unwrap();
+        let second = cache.for_file(&package.join("src/b.ts")).unwrap();
+
+        assert_eq!(first.config_dir, package);
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(cache.for_file(&dir.path().join("other.ts")).is_none());
+    }
+
+    #[test]
+    fn test_project_references_map_out_dir_to_root_dir() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("core")).unwrap();
+        std::fs::create_dir_all(root.join("app")).unwrap();
+        std::fs::write(
+            root.join("core/tsconfig.json"),
+            r#"{ "compilerOptions": { "rootDir": "src", "outDir": "dist" } }"#,
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("app/tsconfig.json"),
+            r#"{ "references": [{ "path": "../core" }] }"#,
+        )
+        .unwrap();
+
+        let aliases = load_tsconfig(&root.join("app")).unwrap();
+        assert_eq!(aliases.projects().len(), 2);
+        assert_eq!(
+            aliases.source_for_output(&root.join("app/../core/dist/utils/index.d.ts")),
+            Some(root.join("core/src/utils/index"))
+        );
+        assert_eq!(aliases.source_for_output(&root.join("core/src/a.ts")), None);
+    }
+
+    #[test]
+    fn test_extends_array_and_config_dir() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("configs")).unwrap();
+        std::fs::create_dir_all(root.join("app")).unwrap();
+        std::fs::write(
+            root.join("configs/tsconfig.base"),
+            r#"{ "compilerOptions": { "baseUrl": "${configDir}/src", "outDir": "out" } }"#,
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("configs/paths.json"),
+            r#"{ "compilerOptions": { "paths": { "@/*": ["${configDir}/src/*"] } } }"#,
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("app/tsconfig.json"),
+            r#"{ "extends": ["../configs/tsconfig.base", "../configs/paths.json"] }"#,
+        )
+        .unwrap();
+
+        let aliases = load_tsconfig(&root.join("app")).unwrap();
+        assert_eq!(aliases.base_url, Some(root.join("app/src")));
+        assert_eq!(aliases.out_dir, Some(root.join("configs/out")));
+        assert_eq!(
+            aliases.resolve_candidates("@/a"),
+            vec![root.join("app/src/a"), root.join("app/src/@/a")]
+        );
+    }
+
+    #[test]
+    fn test_extends_package_from_parent_node_modules() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let package = root.join("node_modules/@acme/tsconfig");
+        std::fs::create_dir_all(&package).unwrap();
+        std::fs::create_dir_all(root.join("packages/web")).unwrap();
+        std::fs::write(
+            package.join("package.json"),
+            r#"{ "name": "@acme/tsconfig", "exports": { "./lib": "./lib.json" } }"#,
+        )
+        .unwrap();
+        std::fs::write(
+            package.join("lib.json"),
+            r#"{ "compilerOptions": { "paths": { "~/*": ["./shared/*"] } } }"#,
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("packages/web/tsconfig.json"),
+            r#"{ "extends": "@acme/tsconfig/lib" }"#,
+        )
+        .unwrap();
+
+        let aliases = load_tsconfig(&root.join("packages/web")).unwrap();
+        assert_eq!(aliases.paths_dir, package);
+        assert_eq!(
+            resolve_extends("@acme/tsconfig/lib.json", &root.join("packages/web")),
+            Some(package.join("lib.json"))
+        );
+        assert_eq!(resolve_extends("@acme/missing", root), None);
+    }
+
+    #[test]
+    fn test_root_dirs_candidates() {
+        let mut aliases = PathAliases::new(Path::new("/project"));
+        aliases.root_dirs = vec![
+            PathBuf::from("/project/src"),
+            PathBuf::from("/project/generated"),
+            PathBuf::from("/project/src/views"),
+        ];
+
+        assert_eq!(
+            aliases.root_dirs_candidates(Path::new("/project/generated/api/schema")),
+            vec![
+                PathBuf::from("/project/src/api/schema"),
+                PathBuf::from("/project/src/views/api/schema")
+            ]
+        );
+        // The most specific root dir wins
+        assert_eq!(
+            aliases.root_dirs_candidates(Path::new("/project/src/views/home")),
+            vec![
+                PathBuf::from("/project/src/home"),
+                PathBuf::from("/project/generated/home")
+            ]
+        );
+        assert!(aliases
+            .root_dirs_candidates(Path::new("/project/lib/a"))
+            .is_empty());
+    }
+
+    fn aliases_with_paths(paths: &[(&str, &[&str])]) -> PathAliases {
+        let mut aliases = PathAliases::new(Path::new("/project"));
+        for (pattern, replacements) in paths {
+            aliases.paths.insert(
+                pattern.to_string(),
+                replacements.iter().map(PathBuf::from).collect(),
+            );
+        }
+        aliases
+    }
+
+    #[test]
+    fn test_paths_prefer_longest_prefix() {
+        let aliases = aliases_with_paths(&[
+            ("@/*", &["src/*"]),
+            ("@/components/*", &["ui/*", "legacy/components/*"]),
+            ("*.css", &["styles/*.css"]),
+        ]);
+
+        // Same answer on every run, regardless of HashMap order
+        for _ in 0..10 {
+            assert_eq!(
+                aliases.resolve_candidates("@/components/Button"),
+                vec![
+                    PathBuf::from("/project/ui/Button"),
+                    PathBuf::from("/project/legacy/components/Button")
+                ]
+            );
+        }
+        assert_eq!(
+            aliases.resolve_candidates("@/utils"),
+            vec![PathBuf::from("/project/src/utils")]
+        );
+        assert_eq!(
+            aliases.resolve_candidates("theme.css"),
+            vec![PathBuf::from("/project/styles/theme.css")]
+        );
+        assert!(aliases.resolve_candidates("react").is_empty());
+    }
+
+    #[test]
+    fn test_exact_paths_and_base_url() {
+        let mut aliases = aliases_with_paths(&[("config", &["src/config/index"]), ("*", &["*"])]);
+        aliases.base_url = Some(PathBuf::from("/project/src"));
+
+        assert_eq!(
+            aliases.resolve_candidates("config"),
+            vec![
+                PathBuf::from("/project/src/src/config/index"),
+                PathBuf::from("/project/src/config")
+            ]
+        );
+        assert_eq!(
+            aliases.resolve_candidates("lib/a"),
+            vec![
+                PathBuf::from("/project/src/lib/a"),
+                PathBuf::from("/project/src/lib/a")
+            ]
+        );
+    }
 }
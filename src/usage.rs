@@ -0,0 +1,412 @@
+//! Scope-aware usage analysis for imported bindings.
+//!
+//! An ESM cycle only fails at runtime when an imported binding is read while a
+//! module is still being evaluated (`class A extends B`, a top-level call, a static
+//! initializer). Reads inside function bodies happen later and are harmless.
+//! This pass records which names are read during evaluation, and which calls run then.
+//!
+//! It also records every name read in a value position, so imports that are only
+//! used as types (and therefore erased by TypeScript) can be told apart.
+
+use std::collections::HashSet;
+use swc_common::BytePos;
+use swc_ecma_ast::*;
+use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
+
+/// What a module reads and calls while it is being evaluated.
+#[derive(Debug, Default)]
+pub struct ModuleUsage {
+    /// Free identifiers read during module evaluation
+    pub eager_refs: HashSet<String>,
+    /// Start positions of call expressions that run during module evaluation
+    pub eager_calls: HashSet<BytePos>,
+    /// Identifiers read in a value position anywhere in the module (including `export { x }`)
+    pub value_refs: HashSet<String>,
+    /// Whether the module contains JSX (which implicitly reads `React` with the classic runtime)
+    pub has_jsx: bool,
+}
+
+/// Analyzes a parsed module and returns its evaluation-time usage.
+pub fn analyze_usage(module: &Module) -> ModuleUsage {
+    let mut analyzer = UsageAnalyzer::default();
+    module.visit_with(&mut analyzer);
+    analyzer.usage
+}
+
+#[derive(Default)]
+struct UsageAnalyzer {
+    usage: ModuleUsage,
+    /// Nesting depth of function bodies that are not immediately invoked
+    deferred_depth: usize,
+    /// Names declared in nested scopes entered during evaluation, which shadow imports
+    scopes: Vec<HashSet<String>>,
+}
+
+impl UsageAnalyzer {
+    fn is_eager(&self) -> bool {
+        self.deferred_depth == 0
+    }
+
+    fn record_ref(&mut self, ident: &Ident) {
+        if self.is_eager() && !self.scopes.iter().any(|s| s.contains(ident.sym.as_ref())) {
+            self.usage.eager_refs.insert(ident.sym.to_string());
+        }
+        self.usage.value_refs.insert(ident.sym.to_string());
+    }
+
+    /// Runs `f` in a context whose code only executes after module evaluation.
+    fn deferred(&mut self, f: impl FnOnce(&mut Self)) {
+        self.deferred_depth += 1;
+        f(self);
+        self.deferred_depth -= 1;
+    }
+
+    /// Runs `f` with `names` shadowing any outer bindings.
+    /// Scopes only matter while eager, since deferred reads are never recorded.
+    fn scoped(&mut self, names: HashSet<String>, f: impl FnOnce(&mut Self)) {
+        if !self.is_eager() || names.is_empty() {
+            f(self);
+            return;
+        }
+        self.scopes.push(names);
+        f(self);
+        self.scopes.pop();
+    }
+}
+
+impl Visit for UsageAnalyzer {
+    noop_visit_type!();
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        if let Expr::Ident(ident) = expr {
+            self.record_ref(ident);
+        }
+        expr.visit_children_with(self);
+    }
+
+    // `{ foo }` reads `foo`
+    fn visit_prop(&mut self, prop: &Prop) {
+        if let Prop::Shorthand(ident) = prop {
+            self.record_ref(ident);
+        }
+        prop.visit_children_with(self);
+    }
+
+    // `export { foo }` keeps `foo` alive without reading it during evaluation
+    fn visit_named_export(&mut self, export: &NamedExport) {
+        if export.src.is_none() && !export.type_only {
+            for spec in &export.specifiers {
+                if let ExportSpecifier::Named(named) = spec {
+                    if let ModuleExportName::Ident(ident) = &named.orig {
+                        if !named.is_type_only {
+                            self.usage.value_refs.insert(ident.sym.to_string());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn visit_jsx_fragment(&mut self, fragment: &JSXFragment) {
+        self.usage.has_jsx = true;
+        fragment.visit_children_with(self);
+    }
+
+    // `<Button />` reads `Button`; lowercase names are intrinsic elements
+    fn visit_jsx_element_name(&mut self, name: &JSXElementName) {
+        self.usage.has_jsx = true;
+        if let JSXElementName::Ident(ident) = name {
+            if ident.sym.starts_with(|c: char| c.is_ascii_uppercase()) {
+                self.record_ref(ident);
+            }
+        }
+        name.visit_children_with(self);
+    }
+
+    // `<ns.Button />` reads `ns`
+    fn visit_jsx_object(&mut self, obj: &JSXObject) {
+        if let JSXObject::Ident(ident) = obj {
+            self.record_ref(ident);
+        }
+        obj.visit_children_with(self);
+    }
+
+    fn visit_function(&mut self, function: &Function) {
+        // Decorators run when the class is defined; the body runs when called
+        function.decorators.visit_with(self);
+        self.deferred(|this| {
+            function.params.visit_with(this);
+            function.body.visit_with(this);
+        });
+    }
+
+    fn visit_arrow_expr(&mut self, arrow: &ArrowExpr) {
+        self.deferred(|this| arrow.visit_children_with(this));
+    }
+
+    fn visit_constructor(&mut self, constructor: &Constructor) {
+        constructor.key.visit_with(self);
+        self.deferred(|this| {
+            constructor.params.visit_with(this);
+            constructor.body.visit_with(this);
+        });
+    }
+
+    fn visit_getter_prop(&mut self, prop: &GetterProp) {
+        prop.key.visit_with(self);
+        self.deferred(|this| prop.body.visit_with(this));
+    }
+
+    fn visit_setter_prop(&mut self, prop: &SetterProp) {
+        prop.key.visit_with(self);
+        self.deferred(|this| {
+            prop.param.visit_with(this);
+            prop.body.visit_with(this);
+        });
+    }
+
+    // Instance field initializers run on construction; static ones run with the class
+    fn visit_class_prop(&mut self, prop: &ClassProp) {
+        prop.decorators.visit_with(self);
+        prop.key.visit_with(self);
+        if prop.is_static {
+            prop.value.visit_with(self);
+        } else {
+            self.deferred(|this| prop.value.visit_with(this));
+        }
+    }
+
+    fn visit_private_prop(&mut self, prop: &PrivateProp) {
+        prop.decorators.visit_with(self);
+        if prop.is_static {
+            prop.value.visit_with(self);
+        } else {
+            self.deferred(|this| prop.value.visit_with(this));
+        }
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if self.is_eager() {
+            self.usage.eager_calls.insert(call.span.lo);
+        }
+
+        // Immediately invoked function expressions run their body right away
+        match &call.callee {
+            Callee::Expr(callee) => match unwrap_parens(callee) {
+                Expr::Fn(fn_expr) => {
+                    let function = &fn_expr.function;
+                    let mut names = params_names(function.params.iter().map(|p| &p.pat));
+                    if let Some(body) = &function.body {
+                        names.extend(declared_names(&body.stmts));
+                    }
+                    self.scoped(names, |this| {
+                        function.params.visit_with(this);
+                        if let Some(body) = &function.body {
+                            body.stmts.visit_with(this);
+                        }
+                    });
+                }
+                Expr::Arrow(arrow) => {
+                    let mut names = params_names(arrow.params.iter());
+                    if let BlockStmtOrExpr::BlockStmt(body) = &*arrow.body {
+                        names.extend(declared_names(&body.stmts));
+                    }
+                    self.scoped(names, |this| {
+                        arrow.params.visit_with(this);
+                        match &*arrow.body {
+                            BlockStmtOrExpr::BlockStmt(body) => body.stmts.visit_with(this),
+                            BlockStmtOrExpr::Expr(expr) => expr.visit_with(this),
+                        }
+                    });
+                }
+                _ => callee.visit_with(self),
+            },
+            callee => callee.visit_with(self),
+        }
+
+        call.args.visit_with(self);
+    }
+
+    fn visit_block_stmt(&mut self, block: &BlockStmt) {
+        let names = declared_names(&block.stmts);
+        self.scoped(names, |this| block.stmts.visit_with(this));
+    }
+
+    fn visit_for_stmt(&mut self, stmt: &ForStmt) {
+        let names = match &stmt.init {
+            Some(VarDeclOrExpr::VarDecl(decl)) => var_decl_names(decl),
+            _ => HashSet::new(),
+        };
+        self.scoped(names, |this| stmt.visit_children_with(this));
+    }
+
+    fn visit_for_of_stmt(&mut self, stmt: &ForOfStmt) {
+        stmt.right.visit_with(self);
+        let names = for_head_names(&stmt.left);
+        self.scoped(names, |this| {
+            stmt.left.visit_with(this);
+            stmt.body.visit_with(this);
+        });
+    }
+
+    fn visit_for_in_stmt(&mut self, stmt: &ForInStmt) {
+        stmt.right.visit_with(self);
+        let names = for_head_names(&stmt.left);
+        self.scoped(names, |this| {
+            stmt.left.visit_with(this);
+            stmt.body.visit_with(this);
+        });
+    }
+
+    fn visit_catch_clause(&mut self, clause: &CatchClause) {
+        let names = params_names(clause.param.iter());
+        self.scoped(names, |this| clause.visit_children_with(this));
+    }
+}
+
+fn unwrap_parens(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Paren(paren) => unwrap_parens(&paren.expr),
+        _ => expr,
+    }
+}
+
+/// Names declared directly in a list of statements.
+fn declared_names(stmts: &[Stmt]) -> HashSet<String> {
+    let mut names = HashSet::new();
+    for stmt in stmts {
+        match stmt {
+            Stmt::Decl(Decl::Var(decl)) => names.extend(var_decl_names(decl)),
+            Stmt::Decl(Decl::Fn(decl)) => {
+                names.insert(decl.ident.sym.to_string());
+            }
+            Stmt::Decl(Decl::Class(decl)) => {
+                names.insert(decl.ident.sym.to_string());
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+fn var_decl_names(decl: &VarDecl) -> HashSet<String> {
+    params_names(decl.decls.iter().map(|d| &d.name))
+}
+
+fn for_head_names(head: &ForHead) -> HashSet<String> {
+    match head {
+        ForHead::VarDecl(decl) => var_decl_names(decl),
+        _ => HashSet::new(),
+    }
+}
+
+fn params_names<'a>(pats: impl Iterator<Item = &'a Pat>) -> HashSet<String> {
+    let mut names = HashSet::new();
+    for pat in pats {
+        collect_pat_names(pat, &mut names);
+    }
+    names
+}
+
+fn collect_pat_names(pat: &Pat, names: &mut HashSet<String>) {
+    match pat {
+        Pat::Ident(ident) => {
+            names.insert(ident.id.sym.to_string());
+        }
+        Pat::Array(array) => {
+            for elem in array.elems.iter().flatten() {
+                collect_pat_names(elem, names);
+            }
+        }
+        Pat::Object(obj) => {
+            for prop in &obj.props {
+                match prop {
+                    ObjectPatProp::KeyValue(kv) => collect_pat_names(&kv.value, names),
+                    ObjectPatProp::Assign(assign) => {
+                        names.insert(assign.key.sym.to_string());
+                    }
+                    ObjectPatProp::Rest(rest) => collect_pat_names(&rest.arg, names),
+                }
+            }
+        }
+        Pat::Rest(rest) => collect_pat_names(&rest.arg, names),
+        Pat::Assign(assign) => collect_pat_names(&assign.left, names),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swc_common::sync::Lrc;
+    use swc_common::{FileName, SourceMap};
+    use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsSyntax};
+
+    fn usage_of(source: &str) -> ModuleUsage {
+        let cm: Lrc<SourceMap> = Default::default();
+        let fm = cm.new_source_file(Lrc::new(FileName::Anon), source.to_string());
+        let lexer = Lexer::new(
+            Syntax::Typescript(TsSyntax {
+                tsx: true,
+                decorators: true,
+                ..Default::default()
+            }),
+            EsVersion::Es2022,
+            StringInput::from(&*fm),
+            None,
+        );
+        let module = Parser::new_from(lexer).parse_module().unwrap();
+        analyze_usage(&module)
+    }
+
+    #[test]
+    fn test_top_level_reads_are_eager() {
+        let usage = usage_of(
+            "class A extends Base {}\nconst x = helper();\nexport default Config;\nconst el = <Widget />;",
+        );
+
+        for name in ["Base", "helper", "Config", "Widget"] {
+            assert!(usage.eager_refs.contains(name), "{} should be eager", name);
+        }
+    }
+
+    #[test]
+    fn test_function_body_reads_are_deferred() {
+        let usage = usage_of(
+            "function f() { return helper(); }\nconst g = () => Other;\nclass C { field = Field; static s = Static; method() { return Method; } }",
+        );
+
+        assert!(!usage.eager_refs.contains("helper"));
+        assert!(!usage.eager_refs.contains("Other"));
+        assert!(!usage.eager_refs.contains("Field"));
+        assert!(!usage.eager_refs.contains("Method"));
+        assert!(usage.eager_refs.contains("Static"));
+    }
+
+    #[test]
+    fn test_value_refs_exclude_type_positions() {
+        let usage = usage_of(
+            "let a: TypeOnly = make();\nlet b: typeof Queried;\nclass C implements Iface {}\nfunction f() { return Value; }\nexport { Reexported };",
+        );
+
+        assert!(usage.value_refs.contains("make"));
+        assert!(usage.value_refs.contains("Value"));
+        assert!(usage.value_refs.contains("Reexported"));
+        assert!(!usage.value_refs.contains("TypeOnly"));
+        assert!(!usage.value_refs.contains("Queried"));
+        assert!(!usage.value_refs.contains("Iface"));
+        assert!(!usage.has_jsx);
+    }
+
+    #[test]
+    fn test_iife_and_shadowing() {
+        let usage = usage_of(
+            "(() => { setup(); })();\n{ const Shadowed = 1; use(Shadowed); }\n(function (Param) { Param(); })(1);",
+        );
+
+        assert!(usage.eager_refs.contains("setup"));
+        assert!(usage.eager_refs.contains("use"));
+        assert!(!usage.eager_refs.contains("Shadowed"));
+        assert!(!usage.eager_refs.contains("Param"));
+    }
+}
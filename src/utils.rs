@@ -1,7 +1,7 @@
 //! Common utility functions used across the crate.
 
 use sha2::{Digest, Sha256};
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 
 /// Encode bytes as a hexadecimal string.
 pub fn hex_encode(bytes: &[u8]) -> String {
@@ -22,7 +22,7 @@ pub fn hash_strings(strings: &[String], hex_len: usize) -> String {
     }
 
     let result = hasher.finalize();
-    let bytes_needed = (hex_len + 1) / 2;
+    let bytes_needed = hex_len.div_ceil(2);
     hex_encode(&result[..bytes_needed.min(result.len())])[..hex_len].to_string()
 }
 
@@ -35,13 +35,122 @@ pub fn relative_path_string(path: &Path, root: &Path) -> String {
         .to_string()
 }
 
-/// List of supported file extensions for TypeScript/JavaScript files.
-pub const EXTENSIONS: &[&str] = &[".tsx", ".ts", ".jsx", ".js", ".cjs", ".mjs"];
+/// Like [`relative_path_string`], but paths outside root are shown with `..`
+/// components (e.g. `../ui/src/index.ts`) instead of as absolute paths.
+pub fn path_from_root(path: &Path, root: &Path) -> String {
+    let common = path
+        .components()
+        .zip(root.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+    if common == 0 {
+        return path.display().to_string();
+    }
+
+    let mut relative = PathBuf::new();
+    for _ in root.components().skip(common) {
+        relative.push("..");
+    }
+    relative.extend(path.components().skip(common));
+    relative.display().to_string()
+}
+
+/// Lexically normalizes a path, resolving `.` and `..` components without
+/// touching the filesystem (so it works for paths that don't exist yet).
+pub fn clean_path(path: &Path) -> PathBuf {
+    let mut cleaned = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match cleaned.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    cleaned.pop();
+                }
+                // `..` at the root is the root itself
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                // Leading `..`s can't be resolved lexically, so they're kept
+                _ => cleaned.push(component),
+            },
+            _ => cleaned.push(component),
+        }
+    }
+    cleaned
+}
+
+/// List of supported file extensions for TypeScript/JavaScript files
+/// and single-file components (Vue, Svelte, Astro).
+pub const EXTENSIONS: &[&str] = &[
+    ".tsx", ".ts", ".d.ts", ".jsx", ".js", ".cjs", ".mjs", ".mts", ".d.mts", ".cts", ".d.cts",
+    ".vue", ".svelte", ".astro",
+];
+
+/// TypeScript source extensions to try for a JavaScript import specifier.
+///
+/// TypeScript lets `./foo.js` refer to `./foo.ts`, `./foo.mjs` to `./foo.mts`, and so on.
+pub fn ts_extensions_for_js(ext: &str) -> &'static [&'static str] {
+    match ext {
+        "js" => &[".ts", ".tsx", ".d.ts"],
+        "jsx" => &[".tsx"],
+        "mjs" => &[".mts", ".d.mts"],
+        "cjs" => &[".cts", ".d.cts"],
+        _ => &[],
+    }
+}
+
+/// Returns true if the path is a TypeScript declaration file (`.d.ts`, `.d.mts`, `.d.cts`).
+pub fn is_declaration_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| {
+            [".d.ts", ".d.mts", ".d.cts"]
+                .iter()
+                .any(|ext| name.ends_with(ext))
+        })
+        .unwrap_or(false)
+}
+
+/// Returns true if the path has one of the supported [`EXTENSIONS`].
+pub fn has_supported_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| EXTENSIONS.iter().any(|e| e[1..] == *ext))
+        .unwrap_or(false)
+}
+
+/// Splits `@scope/name/sub/path` into the package name and the remaining subpath.
+pub fn split_package_name(specifier: &str) -> (&str, &str) {
+    let segments = if specifier.starts_with('@') { 2 } else { 1 };
+    match specifier.match_indices('/').nth(segments - 1) {
+        Some((i, _)) => (&specifier[..i], &specifier[i + 1..]),
+        None => (specifier, ""),
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_split_package_name() {
+        assert_eq!(split_package_name("pkg"), ("pkg", ""));
+        assert_eq!(split_package_name("pkg/base.json"), ("pkg", "base.json"));
+        assert_eq!(split_package_name("@scope/pkg"), ("@scope/pkg", ""));
+        assert_eq!(split_package_name("@scope/pkg/a/b"), ("@scope/pkg", "a/b"));
+    }
+
+    #[test]
+    fn test_clean_path() {
+        assert_eq!(
+            clean_path(Path::new("/repo/packages/app/../core/./dist")),
+            PathBuf::from("/repo/packages/core/dist")
+        );
+        assert_eq!(clean_path(Path::new("../a/./b")), PathBuf::from("../a/b"));
+        assert_eq!(clean_path(Path::new("../../a")), PathBuf::from("../../a"));
+        assert_eq!(clean_path(Path::new("a/../../b")), PathBuf::from("../b"));
+        assert_eq!(clean_path(Path::new("/..")), PathBuf::from("/"));
+        assert_eq!(clean_path(Path::new("/a/../../b")), PathBuf::from("/b"));
+    }
+
     #[test]
     fn test_hex_encode() {
         assert_eq!(hex_encode(&[0x00, 0xff, 0xab]), "00ffab");
@@ -81,4 +190,35 @@ mod tests {
             "/other/path/file.ts"
         );
     }
+
+    #[test]
+    fn test_path_from_root() {
+        let root = PathBuf::from("/repo/packages/web");
+        assert_eq!(
+            path_from_root(Path::new("/repo/packages/web/src/a.ts"), &root),
+            "src/a.ts"
+        );
+        assert_eq!(
+            path_from_root(Path::new("/repo/packages/ui/src/index.ts"), &root),
+            "../ui/src/index.ts"
+        );
+    }
+
+    #[test]
+    fn test_has_supported_extension() {
+        assert!(has_supported_extension(Path::new("src/App.vue")));
+        assert!(has_supported_extension(Path::new("src/index.ts")));
+        assert!(!has_supported_extension(Path::new("src/styles.css")));
+        assert!(!has_supported_extension(Path::new("Makefile")));
+        assert!(has_supported_extension(Path::new("src/esm.mts")));
+        assert!(has_supported_extension(Path::new("src/globals.d.ts")));
+    }
+
+    #[test]
+    fn test_is_declaration_file() {
+        assert!(is_declaration_file(Path::new("types.d.ts")));
+        assert!(is_declaration_file(Path::new("src/env.d.mts")));
+        assert!(!is_declaration_file(Path::new("src/index.ts")));
+        assert!(!is_declaration_file(Path::new("src/d.ts")));
+    }
 }
@@ -2,6 +2,7 @@
 //!
 //! This module is only available when the `watch` feature is enabled.
 
+use crate::utils::has_supported_extension;
 use notify::RecursiveMode;
 use notify_debouncer_mini::{new_debouncer, DebouncedEventKind};
 use std::path::Path;
@@ -39,13 +40,7 @@ where
                     let path = &event.path;
 
                     // Check if file has a relevant extension
-                    let is_relevant_ext = path
-                        .extension()
-                        .and_then(|e| e.to_str())
-                        .map(|ext| matches!(ext, "ts" | "tsx" | "js" | "jsx" | "cjs" | "mjs"))
-                        .unwrap_or(false);
-
-                    if !is_relevant_ext {
+                    if !has_supported_extension(path) {
                         return false;
                     }
 
@@ -1,8 +1,13 @@
-use glob::glob;
+use crate::filesystem::NearestAncestorCache;
+use crate::tsconfig::{load_tsconfig, strip_jsonc, PathAliases};
+use crate::utils::{clean_path, ts_extensions_for_js, EXTENSIONS};
+use glob::{glob, Pattern};
 use log::debug;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use walkdir::WalkDir;
 
 /// Information about a single workspace package.
 #[derive(Debug, Clone)]
@@ -17,30 +22,462 @@ pub struct PackageInfo {
     pub module: Option<String>,
     /// Package exports configuration
     pub exports: Option<Exports>,
+    /// The package's own tsconfig.json, used to map built output back to sources
+    pub tsconfig: Option<PathAliases>,
 }
 
-/// Represents the package.json "exports" field.
+impl PackageInfo {
+    /// Loads a package from its package.json. Returns None without a `name`.
+    pub fn load(package_json_path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(package_json_path).ok()?;
+        let package_json: PackageJson = serde_json::from_str(&content).ok()?;
+
+        let name = package_json.name?;
+        let path = package_json_path
+            .parent()?
+            .canonicalize()
+            .unwrap_or_else(|_| package_json_path.parent().unwrap().to_path_buf());
+
+        let exports = package_json.exports.and_then(Workspace::parse_exports);
+        let tsconfig = load_tsconfig(&path);
+
+        Some(Self {
+            name,
+            path,
+            main: package_json.main,
+            module: package_json.module,
+            exports,
+            tsconfig,
+        })
+    }
+
+    /// Maps a built entry point (e.g. `dist/index.js`) back to its source file,
+    /// using the package's tsconfig `outDir`/`rootDir`, then a `.map` sourcemap
+    /// next to the built file. Works even if the package hasn't been built.
+    pub fn source_for_built(&self, path: &Path) -> Option<PathBuf> {
+        if let Some(source) = self
+            .tsconfig
+            .as_ref()
+            .and_then(|tsconfig| tsconfig.source_for_output(path))
+            .and_then(|source| find_source_file(&source))
+        {
+            debug!("Mapped built entry {:?} to {:?} via tsconfig", path, source);
+            return Some(source);
+        }
+
+        let source = source_from_sourcemap(path)?;
+        debug!(
+            "Mapped built entry {:?} to {:?} via sourcemap",
+            path, source
+        );
+        Some(source)
+    }
+
+    /// Resolves the package root (`import "pkg"`) to a file.
+    pub fn resolve_entry(&self, conditions: &[String]) -> Option<PathBuf> {
+        // Try exports field first; `null` hides the entry entirely
+        if let Some(exports) = &self.exports {
+            match exports.lookup(".", conditions) {
+                ExportsMatch::Targets(targets) => {
+                    if let Some(resolved) = self.resolve_export_targets(&targets) {
+                        return Some(resolved);
+                    }
+                }
+                ExportsMatch::Excluded => return None,
+                ExportsMatch::NoMatch => {}
+            }
+        }
+
+        // Fall back to module/main/index
+        // Try module field (ESM)
+        if let Some(module) = &self.module {
+            if let Some(path) = self.entry_file(&self.path.join(module)) {
+                debug!("Resolved via module field: {:?}", path);
+                return Some(path);
+            }
+        }
+
+        // Try main field
+        if let Some(main) = &self.main {
+            if let Some(path) = self.entry_file(&self.path.join(main)) {
+                debug!("Resolved via main field: {:?}", path);
+                return Some(path);
+            }
+        }
+
+        // Try common entry points
+        for entry in &["src/index.ts", "src/index.tsx", "index.ts", "index.js"] {
+            let path = self.path.join(entry);
+            if path.exists() {
+                debug!("Resolved via default entry: {:?}", path);
+                return Some(path);
+            }
+        }
+
+        None
+    }
+
+    /// Resolves a subpath of the package (`import "pkg/button"`) to a file.
+    pub fn resolve_subpath(&self, subpath: &str, conditions: &[String]) -> Option<PathBuf> {
+        // Try exports field first (exact subpaths, then wildcard patterns)
+        if let Some(exports) = &self.exports {
+            let export_key = format!("./{}", subpath);
+            match exports.lookup(&export_key, conditions) {
+                ExportsMatch::Targets(targets) => {
+                    if let Some(resolved) = self.resolve_export_targets(&targets) {
+                        return Some(resolved);
+                    }
+                }
+                // Explicitly private (e.g. `"./internal/*": null`)
+                ExportsMatch::Excluded => return None,
+                ExportsMatch::NoMatch => {}
+            }
+        }
+
+        // Fall back to direct file resolution in src/
+        let extensions = ["", ".ts", ".tsx", ".js", ".jsx"];
+        let prefixes = ["src/", ""];
+
+        for prefix in &prefixes {
+            for ext in &extensions {
+                let path = self.path.join(format!("{}{}{}", prefix, subpath, ext));
+                if path.exists() {
+                    debug!("Resolved subpath via direct file: {:?}", path);
+                    return Some(path);
+                }
+
+                // Try as directory with index file
+                let index_path = self
+                    .path
+                    .join(format!("{}{}/index{}", prefix, subpath, ext));
+                if index_path.exists() {
+                    debug!("Resolved subpath via index file: {:?}", index_path);
+                    return Some(index_path);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Returns the file an entry point refers to: its source if it's built output,
+    /// otherwise the file itself if it exists.
+    fn entry_file(&self, path: &Path) -> Option<PathBuf> {
+        self.source_for_built(path)
+            .or_else(|| path.exists().then(|| path.to_path_buf()))
+    }
+
+    /// Returns the first export target (array fallbacks in order) that exists.
+    fn resolve_export_targets(&self, targets: &[String]) -> Option<PathBuf> {
+        for target in targets {
+            let path = self.path.join(target.trim_start_matches("./"));
+            if let Some(path) = self.entry_file(&path) {
+                debug!("Resolved export target: {:?}", path);
+                return Some(path);
+            }
+
+            // Try adding extensions
+            for ext in &[".ts", ".tsx", ".js", ".jsx"] {
+                let with_ext = path.with_extension(ext.trim_start_matches('.'));
+                if with_ext.exists() {
+                    debug!("Resolved export target with extension: {:?}", with_ext);
+                    return Some(with_ext);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Finds the source file for a path mapped out of `outDir`: `index.js` may be
+/// `index.ts`, `index.mjs` may be `index.mts` or `index.ts`, and so on.
+fn find_source_file(path: &Path) -> Option<PathBuf> {
+    if path.is_file() {
+        return Some(path.to_path_buf());
+    }
+
+    let name = path.file_name()?.to_str()?;
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+    let stem = if ext.is_empty() {
+        name
+    } else {
+        &name[..name.len() - ext.len() - 1]
+    };
+    ts_extensions_for_js(ext)
+        .iter()
+        .chain(EXTENSIONS.iter().filter(|e| !e.starts_with(".d.")))
+        .map(|source_ext| path.with_file_name(format!("{}{}", stem, source_ext)))
+        .find(|candidate| candidate.is_file())
+}
+
+/// The subset of a sourcemap needed to find the original files.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SourceMap {
+    source_root: Option<String>,
+    sources: Vec<String>,
+}
+
+/// Reads `<file>.map` and returns the first original source that exists.
+fn source_from_sourcemap(path: &Path) -> Option<PathBuf> {
+    let mut map_path = path.as_os_str().to_owned();
+    map_path.push(".map");
+    let map_path = PathBuf::from(map_path);
+    let content = std::fs::read_to_string(&map_path).ok()?;
+    let map: SourceMap = serde_json::from_str(&content)
+        .map_err(|e| debug!("Failed to parse sourcemap {:?}: {}", map_path, e))
+        .ok()?;
+
+    // Sources are relative to `sourceRoot`, which is relative to the map itself
+    let root = map_path
+        .parent()?
+        .join(map.source_root.as_deref().unwrap_or_default());
+    map.sources
+        .iter()
+        .filter(|source| !source.contains("://"))
+        .map(|source| clean_path(&root.join(source)))
+        .find(|source| source.is_file())
+}
+
+/// Conditions matched when none are configured. `default` always matches.
+pub const DEFAULT_CONDITIONS: &[&str] = &["import", "require"];
+
+/// Represents the package.json "exports" field (also used for "imports").
 #[derive(Debug, Clone)]
 pub enum Exports {
-    /// Simple string export: `"exports": "./dist/index.js"`
-    String(String),
-    /// Object export with subpaths or conditions
-    Object(HashMap<String, ExportValue>),
+    /// Exports for the package root only: `"exports": "./dist/index.js"`,
+    /// a conditions object or an array of fallbacks
+    Main(ExportValue),
+    /// Subpath map: `{".": ..., "./button": ..., "./*": ...}`
+    Subpaths(HashMap<String, ExportValue>),
 }
 
-/// A single export value, which can be a string or conditional object.
+/// A node of an exports tree.
 #[derive(Debug, Clone)]
 pub enum ExportValue {
-    String(String),
-    Conditional(HashMap<String, String>),
+    /// A target path (`"./dist/index.js"`), or a package name in `imports`
+    Target(String),
+    /// Conditions in declaration order; the first one that matches wins
+    Conditional(Vec<(String, ExportValue)>),
+    /// Fallbacks, tried in order
+    Array(Vec<ExportValue>),
+    /// `null`: the subpath is explicitly not exported
+    Null,
+}
+
+impl ExportValue {
+    fn parse(value: serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::String(s) => ExportValue::Target(s),
+            serde_json::Value::Array(items) => {
+                ExportValue::Array(items.into_iter().map(Self::parse).collect())
+            }
+            // Relies on serde_json's `preserve_order`: condition order matters
+            serde_json::Value::Object(conditions) => ExportValue::Conditional(
+                conditions
+                    .into_iter()
+                    .map(|(condition, value)| (condition, Self::parse(value)))
+                    .collect(),
+            ),
+            _ => ExportValue::Null,
+        }
+    }
+
+    /// Returns the targets selected by `conditions`, in fallback order, like Node:
+    /// the first condition (in declaration order) that is active or `default` is
+    /// followed, moving on to the next one if nothing inside it matches.
+    ///
+    /// Returns None if no condition matches, and an empty list if the value is
+    /// excluded with `null`.
+    fn targets(&self, conditions: &[String]) -> Option<Vec<&str>> {
+        match self {
+            ExportValue::Target(target) => Some(vec![target.as_str()]),
+            ExportValue::Null => Some(Vec::new()),
+            ExportValue::Array(items) => {
+                let selected: Vec<_> = items
+                    .iter()
+                    .filter_map(|item| item.targets(conditions))
+                    .collect();
+                (!selected.is_empty()).then(|| selected.concat())
+            }
+            ExportValue::Conditional(entries) => entries
+                .iter()
+                .filter(|(condition, _)| {
+                    condition == "default" || conditions.iter().any(|c| c == condition)
+                })
+                .find_map(|(_, value)| value.targets(conditions)),
+        }
+    }
+}
+
+/// Finds the entry of a subpath map for `key` (e.g. `./button` or `#db`).
+/// An exact entry wins; otherwise the `*` pattern with the longest prefix, then the
+/// longest key, is used, like Node. Returns the entry with the text matched by `*`.
+fn match_subpath<'a>(
+    map: &'a HashMap<String, ExportValue>,
+    key: &'a str,
+) -> Option<(&'a ExportValue, Option<&'a str>)> {
+    if let Some(value) = map.get(key).filter(|_| !key.contains('*')) {
+        return Some((value, None));
+    }
+
+    map.iter()
+        .filter_map(|(pattern, value)| {
+            let (prefix, suffix) = pattern.split_once('*')?;
+            if suffix.contains('*') || key.len() < pattern.len() {
+                return None;
+            }
+            let matched = key.strip_prefix(prefix)?.strip_suffix(suffix)?;
+            Some((prefix.len(), pattern, value, matched))
+        })
+        .max_by(|a, b| {
+            (a.0, a.1.len())
+                .cmp(&(b.0, b.1.len()))
+                .then_with(|| b.1.cmp(a.1))
+        })
+        .map(|(_, _, value, matched)| (value, Some(matched)))
+}
+
+/// Result of looking a subpath up in an exports map.
+enum ExportsMatch {
+    /// Targets to try in order, with `*` already substituted
+    Targets(Vec<String>),
+    /// The subpath is excluded with `null`
+    Excluded,
+    /// No entry or condition applies
+    NoMatch,
+}
+
+impl Exports {
+    /// Looks up a subpath (`.`, `./button` or `#db`) with the given conditions.
+    fn lookup(&self, key: &str, conditions: &[String]) -> ExportsMatch {
+        let (value, matched) = match self {
+            Exports::Main(value) if key == "." => (value, None),
+            Exports::Main(_) => return ExportsMatch::NoMatch,
+            Exports::Subpaths(map) => match match_subpath(map, key) {
+                Some(found) => found,
+                None => return ExportsMatch::NoMatch,
+            },
+        };
+
+        match value.targets(conditions) {
+            None => ExportsMatch::NoMatch,
+            Some(targets) if targets.is_empty() => ExportsMatch::Excluded,
+            Some(targets) => ExportsMatch::Targets(
+                targets
+                    .into_iter()
+                    .map(|target| match matched {
+                        Some(matched) => target.replace('*', matched),
+                        None => target.to_string(),
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Where a package.json `imports` entry points.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportsTarget {
+    /// A file inside the package (`"#db": "./src/db.js"`), not yet probed for extensions
+    Path(PathBuf),
+    /// Another package (`"#dep": "dep-polyfill"`)
+    Package(String),
+}
+
+/// Resolves Node subpath imports (`import db from '#internal/db'`) with the
+/// `imports` field of the package.json closest to the importing file.
+/// Package scopes are cached per directory, so each package.json is read once.
+#[derive(Debug, Default)]
+pub struct PackageImports {
+    /// Directory -> nearest package.json `imports` map at or above it
+    scopes: NearestAncestorCache<Option<Arc<PackageScope>>>,
+}
+
+/// A package.json and its `imports` field.
+#[derive(Debug)]
+struct PackageScope {
+    path: PathBuf,
+    imports: Exports,
+}
+
+impl PackageImports {
+    /// Resolves a `#` specifier imported from `importer`, returning the targets to
+    /// try in order. Entries are matched like `exports`: exact keys, then the most
+    /// specific `*` pattern, with nested conditions, arrays and `null`.
+    pub fn resolve(
+        &self,
+        importer: &Path,
+        specifier: &str,
+        conditions: &[String],
+    ) -> Vec<ImportsTarget> {
+        let Some(scope) = self.scope_for(importer) else {
+            return Vec::new();
+        };
+        let ExportsMatch::Targets(targets) = scope.imports.lookup(specifier, conditions) else {
+            return Vec::new();
+        };
+
+        debug!(
+            "Resolved subpath import '{}' to {:?} via {:?}",
+            specifier, targets, scope.path
+        );
+        targets
+            .into_iter()
+            .map(|target| {
+                if target.starts_with("./") || target.starts_with("../") {
+                    ImportsTarget::Path(scope.path.join(target))
+                } else {
+                    ImportsTarget::Package(target)
+                }
+            })
+            .collect()
+    }
+
+    /// Finds the package scope (nearest package.json) of a file.
+    fn scope_for(&self, file: &Path) -> Option<Arc<PackageScope>> {
+        // The nearest package.json is the scope, even if it can't be read
+        self.scopes
+            .get(file, |dir| {
+                let candidate = dir.join("package.json");
+                candidate
+                    .is_file()
+                    .then(|| Self::load_scope(&candidate).map(Arc::new))
+            })
+            .flatten()
+    }
+
+    fn load_scope(package_json_path: &Path) -> Option<PackageScope> {
+        let content = std::fs::read_to_string(package_json_path).ok()?;
+        let package_json: PackageJson = serde_json::from_str(&content).ok()?;
+
+        let imports = match package_json.imports.and_then(Workspace::parse_exports) {
+            Some(Exports::Subpaths(map)) => map
+                .into_iter()
+                .filter(|(key, _)| key.starts_with('#'))
+                .collect(),
+            _ => HashMap::new(),
+        };
+        let imports = Exports::Subpaths(imports);
+
+        Some(PackageScope {
+            path: package_json_path.parent()?.to_path_buf(),
+            imports,
+        })
+    }
 }
 
 /// Workspace configuration containing all discovered packages.
 #[derive(Debug)]
 pub struct Workspace {
-    /// Root directory of the workspace (retained for potential future use)
-    #[allow(dead_code)]
+    /// Root directory of the workspace (may be above the scanned directory)
     pub root: PathBuf,
+    /// How the packages were found
+    pub format: WorkspaceFormat,
     /// Map of package names to their info
     pub packages: HashMap<String, PackageInfo>,
 }
@@ -52,6 +489,7 @@ struct PackageJson {
     main: Option<String>,
     module: Option<String>,
     exports: Option<serde_json::Value>,
+    imports: Option<serde_json::Value>,
     workspaces: Option<WorkspacesField>,
 }
 
@@ -72,6 +510,89 @@ impl WorkspacesField {
     }
 }
 
+/// Which monorepo tool's configuration the packages were found with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkspaceFormat {
+    /// `workspaces` in package.json
+    Npm,
+    /// `workspaces` in package.json, with a yarn.lock
+    Yarn,
+    /// `workspaces` in package.json, with a bun.lock(b)
+    Bun,
+    /// pnpm-workspace.yaml
+    Pnpm,
+    /// `packages` in lerna.json
+    Lerna,
+    /// `projects` in rush.json
+    Rush,
+    /// workspace.json or project.json files
+    Nx,
+}
+
+impl std::fmt::Display for WorkspaceFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            WorkspaceFormat::Npm => "npm workspaces",
+            WorkspaceFormat::Yarn => "Yarn workspaces",
+            WorkspaceFormat::Bun => "Bun workspaces",
+            WorkspaceFormat::Pnpm => "pnpm-workspace.yaml",
+            WorkspaceFormat::Lerna => "lerna.json",
+            WorkspaceFormat::Rush => "rush.json",
+            WorkspaceFormat::Nx => "Nx projects",
+        })
+    }
+}
+
+/// lerna.json structure.
+#[derive(Debug, Deserialize)]
+struct LernaJson {
+    packages: Option<Vec<String>>,
+}
+
+/// rush.json structure (JSONC).
+#[derive(Debug, Deserialize)]
+struct RushJson {
+    projects: Vec<RushProject>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RushProject {
+    package_name: String,
+    project_folder: String,
+}
+
+/// Nx workspace.json structure.
+#[derive(Debug, Deserialize)]
+struct NxWorkspaceJson {
+    projects: HashMap<String, NxProject>,
+}
+
+/// A workspace.json project: its directory, or its configuration.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum NxProject {
+    Path(String),
+    Config { root: String },
+}
+
+/// Nx project.json structure.
+#[derive(Debug, Deserialize)]
+struct NxProjectJson {
+    name: Option<String>,
+}
+
+/// Directories never searched for Nx projects.
+fn is_skipped_dir(entry: &walkdir::DirEntry) -> bool {
+    entry.file_type().is_dir()
+        && entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| name == "node_modules" || name == "dist" || name.starts_with('.'))
+        && entry.depth() > 0
+}
+
 /// pnpm-workspace.yaml structure.
 #[derive(Debug, Deserialize)]
 struct PnpmWorkspace {
@@ -79,15 +600,32 @@ struct PnpmWorkspace {
 }
 
 impl Workspace {
-    /// Detects and loads workspace configuration from the given root directory.
-    /// Tries package.json workspaces first, then pnpm-workspace.yaml.
-    pub fn detect(root: &Path) -> Option<Self> {
-        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    /// Finds the workspace enclosing a directory, searching it and then each parent
+    /// (like the config file search), so scanning `packages/web/src` still
+    /// resolves imports against the whole monorepo.
+    pub fn detect(start_dir: &Path) -> Option<Self> {
+        let start_dir = start_dir
+            .canonicalize()
+            .unwrap_or_else(|_| start_dir.to_path_buf());
+
+        let workspace = start_dir.ancestors().find_map(Self::detect_at);
+        if workspace.is_none() {
+            debug!(
+                "No workspace configuration found in or above {:?}",
+                start_dir
+            );
+        }
+        workspace
+    }
 
+    /// Loads workspace configuration from a directory, if it is a workspace root.
+    /// Tries package.json workspaces first, then pnpm-workspace.yaml, lerna.json,
+    /// rush.json and Nx.
+    fn detect_at(root: &Path) -> Option<Self> {
         // Try package.json workspaces field (npm/yarn)
         let package_json_path = root.join("package.json");
         if package_json_path.exists() {
-            if let Some(workspace) = Self::from_package_json(&root, &package_json_path) {
+            if let Some(workspace) = Self::from_package_json(root, &package_json_path) {
                 return Some(workspace);
             }
         }
@@ -95,12 +633,32 @@ impl Workspace {
         // Try pnpm-workspace.yaml
         let pnpm_workspace_path = root.join("pnpm-workspace.yaml");
         if pnpm_workspace_path.exists() {
-            if let Some(workspace) = Self::from_pnpm_workspace(&root, &pnpm_workspace_path) {
+            if let Some(workspace) = Self::from_pnpm_workspace(root, &pnpm_workspace_path) {
+                return Some(workspace);
+            }
+        }
+
+        // Try lerna.json (without `useWorkspaces`)
+        let lerna_path = root.join("lerna.json");
+        if lerna_path.exists() {
+            if let Some(workspace) = Self::from_lerna(root, &lerna_path) {
                 return Some(workspace);
             }
         }
 
-        debug!("No workspace configuration found in {:?}", root);
+        // Try rush.json
+        let rush_path = root.join("rush.json");
+        if rush_path.exists() {
+            if let Some(workspace) = Self::from_rush(root, &rush_path) {
+                return Some(workspace);
+            }
+        }
+
+        // Try Nx (nx.json marks the root)
+        if root.join("nx.json").exists() {
+            return Self::from_nx(root);
+        }
+
         None
     }
 
@@ -113,8 +671,17 @@ impl Workspace {
             return None;
         }
 
-        debug!("Found npm/yarn workspaces: {:?}", patterns);
-        Some(Self::from_patterns(root, &patterns))
+        // npm, Yarn and Bun share the `workspaces` field; the lockfile tells them apart
+        let format = if root.join("bun.lockb").exists() || root.join("bun.lock").exists() {
+            WorkspaceFormat::Bun
+        } else if root.join("yarn.lock").exists() {
+            WorkspaceFormat::Yarn
+        } else {
+            WorkspaceFormat::Npm
+        };
+
+        debug!("Found {} workspaces: {:?}", format, patterns);
+        Some(Self::from_patterns(root, &patterns, format))
     }
 
     fn from_pnpm_workspace(root: &Path, path: &Path) -> Option<Self> {
@@ -127,13 +694,127 @@ impl Workspace {
         }
 
         debug!("Found pnpm workspaces: {:?}", patterns);
-        Some(Self::from_patterns(root, &patterns))
+        Some(Self::from_patterns(root, &patterns, WorkspaceFormat::Pnpm))
     }
 
-    fn from_patterns(root: &Path, patterns: &[String]) -> Self {
+    fn from_lerna(root: &Path, path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        let lerna: LernaJson = serde_json::from_str(&content).ok()?;
+
+        // Lerna defaults to `packages/*` when `packages` is omitted
+        let patterns = lerna
+            .packages
+            .unwrap_or_else(|| vec!["packages/*".to_string()]);
+
+        debug!("Found Lerna packages: {:?}", patterns);
+        Some(Self::from_patterns(root, &patterns, WorkspaceFormat::Lerna))
+    }
+
+    fn from_rush(root: &Path, path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        let rush: RushJson = serde_json::from_str(&strip_jsonc(&content))
+            .map_err(|e| log::warn!("Failed to parse '{}': {}", path.display(), e))
+            .ok()?;
+
         let mut packages = HashMap::new();
+        for project in rush.projects {
+            let package_json_path = root.join(&project.project_folder).join("package.json");
+            match PackageInfo::load(&package_json_path) {
+                Some(info) => {
+                    debug!("Found Rush project: {} at {:?}", info.name, info.path);
+                    packages.insert(info.name.clone(), info);
+                }
+                None => debug!(
+                    "Could not load Rush project '{}' from {:?}",
+                    project.package_name, package_json_path
+                ),
+            }
+        }
 
-        for pattern in patterns {
+        Some(Workspace {
+            root: root.to_path_buf(),
+            format: WorkspaceFormat::Rush,
+            packages,
+        })
+    }
+
+    /// Nx lists projects in `workspace.json`, or in a `project.json` per project.
+    /// Projects without a package.json are registered under their Nx name.
+    fn from_nx(root: &Path) -> Option<Self> {
+        let mut projects: Vec<(Option<String>, PathBuf)> = Vec::new();
+
+        let workspace_json = root.join("workspace.json");
+        if let Some(workspace) = std::fs::read_to_string(&workspace_json)
+            .ok()
+            .and_then(|content| serde_json::from_str::<NxWorkspaceJson>(&content).ok())
+        {
+            for (name, project) in workspace.projects {
+                let dir = match project {
+                    NxProject::Path(dir) => dir,
+                    NxProject::Config { root } => root,
+                };
+                projects.push((Some(name), root.join(dir)));
+            }
+        } else {
+            let entries = WalkDir::new(root)
+                .into_iter()
+                .filter_entry(|e| !is_skipped_dir(e))
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_file() && e.file_name() == "project.json");
+            for entry in entries {
+                let name = std::fs::read_to_string(entry.path())
+                    .ok()
+                    .and_then(|content| serde_json::from_str::<NxProjectJson>(&content).ok())
+                    .and_then(|project| project.name);
+                if let Some(dir) = entry.path().parent() {
+                    projects.push((name, dir.to_path_buf()));
+                }
+            }
+        }
+
+        let mut packages = HashMap::new();
+        for (nx_name, dir) in projects {
+            let info = PackageInfo::load(&dir.join("package.json")).or_else(|| {
+                let path = dir.canonicalize().unwrap_or_else(|_| dir.clone());
+                Some(PackageInfo {
+                    name: nx_name?,
+                    tsconfig: load_tsconfig(&path),
+                    path,
+                    main: None,
+                    module: None,
+                    exports: None,
+                })
+            });
+            if let Some(info) = info {
+                debug!("Found Nx project: {} at {:?}", info.name, info.path);
+                packages.insert(info.name.clone(), info);
+            }
+        }
+
+        Some(Workspace {
+            root: root.to_path_buf(),
+            format: WorkspaceFormat::Nx,
+            packages,
+        })
+    }
+
+    /// Finds packages matching workspace globs. Patterns starting with `!`
+    /// exclude matching packages (e.g. `!packages/legacy/**`).
+    fn from_patterns(root: &Path, patterns: &[String], format: WorkspaceFormat) -> Self {
+        let mut packages = HashMap::new();
+
+        let (exclude, include): (Vec<_>, Vec<_>) = patterns
+            .iter()
+            .partition(|pattern| pattern.starts_with('!'));
+        let exclude: Vec<Pattern> = exclude
+            .iter()
+            .filter_map(|pattern| {
+                let pattern = pattern[1..].trim_start_matches("./").trim_end_matches('/');
+                Pattern::new(pattern).ok()
+            })
+            .collect();
+
+        for pattern in include {
             // Convert workspace pattern to glob pattern
             let glob_pattern = root.join(pattern).join("package.json");
             let glob_str = glob_pattern.to_string_lossy();
@@ -142,7 +823,21 @@ impl Workspace {
 
             if let Ok(entries) = glob(&glob_str) {
                 for entry in entries.flatten() {
-                    if let Some(info) = Self::load_package_info(&entry) {
+                    let Some(dir) = entry.parent().and_then(|d| d.strip_prefix(root).ok()) else {
+                        continue;
+                    };
+                    if dir.components().any(|c| c.as_os_str() == "node_modules") {
+                        continue;
+                    }
+                    // `!dir/**` should exclude `dir` itself too
+                    if exclude
+                        .iter()
+                        .any(|p| p.matches_path(dir) || p.matches_path(&dir.join("package.json")))
+                    {
+                        debug!("Excluded package at {:?}", dir);
+                        continue;
+                    }
+                    if let Some(info) = PackageInfo::load(&entry) {
                         debug!("Found package: {} at {:?}", info.name, info.path);
                         packages.insert(info.name.clone(), info);
                     }
@@ -152,269 +847,46 @@ impl Workspace {
 
         Workspace {
             root: root.to_path_buf(),
+            format,
             packages,
         }
     }
 
-    fn load_package_info(package_json_path: &Path) -> Option<PackageInfo> {
-        let content = std::fs::read_to_string(package_json_path).ok()?;
-        let package_json: PackageJson = serde_json::from_str(&content).ok()?;
-
-        let name = package_json.name?;
-        let path = package_json_path
-            .parent()?
-            .canonicalize()
-            .unwrap_or_else(|_| package_json_path.parent().unwrap().to_path_buf());
-
-        let exports = package_json.exports.and_then(Self::parse_exports);
-
-        Some(PackageInfo {
-            name,
-            path,
-            main: package_json.main,
-            module: package_json.module,
-            exports,
-        })
-    }
-
     fn parse_exports(value: serde_json::Value) -> Option<Exports> {
         match value {
-            serde_json::Value::String(s) => Some(Exports::String(s)),
-            serde_json::Value::Object(obj) => {
-                let mut map = HashMap::new();
-                for (key, val) in obj {
-                    let export_value = match val {
-                        serde_json::Value::String(s) => ExportValue::String(s),
-                        serde_json::Value::Object(cond_obj) => {
-                            let mut conditions = HashMap::new();
-                            for (cond_key, cond_val) in cond_obj {
-                                if let serde_json::Value::String(s) = cond_val {
-                                    conditions.insert(cond_key, s);
-                                }
-                            }
-                            ExportValue::Conditional(conditions)
-                        }
-                        _ => continue,
-                    };
-                    map.insert(key, export_value);
-                }
-                Some(Exports::Object(map))
+            serde_json::Value::Object(map)
+                if map.keys().any(|k| k.starts_with('.') || k.starts_with('#')) =>
+            {
+                Some(Exports::Subpaths(
+                    map.into_iter()
+                        .map(|(key, value)| (key, ExportValue::parse(value)))
+                        .collect(),
+                ))
             }
-            _ => None,
+            serde_json::Value::Null => None,
+            value => Some(Exports::Main(ExportValue::parse(value))),
         }
     }
 
     /// Resolves a bare package import to a file path.
     /// Returns the resolved path if the import matches a workspace package.
-    pub fn resolve(&self, import: &str) -> Option<PathBuf> {
+    /// `conditions` select among conditional exports (`default` always matches).
+    pub fn resolve(&self, import: &str, conditions: &[String]) -> Option<PathBuf> {
         // Check for exact package match first
         if let Some(info) = self.packages.get(import) {
-            return self.resolve_package_entry(info, ".");
+            return info.resolve_entry(conditions);
         }
 
         // Check for subpath import (e.g., "@acme/ui/button")
         for (name, info) in &self.packages {
             if let Some(subpath) = import.strip_prefix(name) {
                 if subpath.is_empty() {
-                    return self.resolve_package_entry(info, ".");
+                    return info.resolve_entry(conditions);
                 }
                 if let Some(subpath) = subpath.strip_prefix('/') {
-                    return self.resolve_subpath(info, subpath);
-                }
-            }
-        }
-
-        None
-    }
-
-    fn resolve_package_entry(&self, info: &PackageInfo, subpath: &str) -> Option<PathBuf> {
-        // Try exports field first
-        if let Some(exports) = &info.exports {
-            if let Some(resolved) = self.resolve_from_exports(info, exports, subpath) {
-                return Some(resolved);
-            }
-        }
-
-        // For root entry ("."), fall back to module/main/index
-        if subpath == "." {
-            // Try module field (ESM)
-            if let Some(module) = &info.module {
-                let path = info.path.join(module);
-                if path.exists() {
-                    debug!("Resolved via module field: {:?}", path);
-                    return Some(path);
-                }
-            }
-
-            // Try main field
-            if let Some(main) = &info.main {
-                let path = info.path.join(main);
-                if path.exists() {
-                    debug!("Resolved via main field: {:?}", path);
-                    return Some(path);
-                }
-            }
-
-            // Try common entry points
-            for entry in &["src/index.ts", "src/index.tsx", "index.ts", "index.js"] {
-                let path = info.path.join(entry);
-                if path.exists() {
-                    debug!("Resolved via default entry: {:?}", path);
-                    return Some(path);
-                }
-            }
-        }
-
-        None
-    }
-
-    fn resolve_subpath(&self, info: &PackageInfo, subpath: &str) -> Option<PathBuf> {
-        // Try exports field first
-        if let Some(exports) = &info.exports {
-            let export_key = format!("./{}", subpath);
-            if let Some(resolved) = self.resolve_from_exports(info, exports, &export_key) {
-                return Some(resolved);
-            }
-
-            // Try wildcard patterns in exports
-            if let Some(resolved) = self.resolve_wildcard_export(info, exports, subpath) {
-                return Some(resolved);
-            }
-        }
-
-        // Fall back to direct file resolution in src/
-        let extensions = ["", ".ts", ".tsx", ".js", ".jsx"];
-        let prefixes = ["src/", ""];
-
-        for prefix in &prefixes {
-            for ext in &extensions {
-                let path = info.path.join(format!("{}{}{}", prefix, subpath, ext));
-                if path.exists() {
-                    debug!("Resolved subpath via direct file: {:?}", path);
-                    return Some(path);
-                }
-
-                // Try as directory with index file
-                let index_path = info
-                    .path
-                    .join(format!("{}{}/index{}", prefix, subpath, ext));
-                if index_path.exists() {
-                    debug!("Resolved subpath via index file: {:?}", index_path);
-                    return Some(index_path);
-                }
-            }
-        }
-
-        None
-    }
-
-    fn resolve_from_exports(
-        &self,
-        info: &PackageInfo,
-        exports: &Exports,
-        subpath: &str,
-    ) -> Option<PathBuf> {
-        match exports {
-            Exports::String(s) if subpath == "." => {
-                let path = info.path.join(s.trim_start_matches("./"));
-                if path.exists() {
-                    return Some(path);
+                    return info.resolve_subpath(subpath, conditions);
                 }
             }
-            Exports::Object(map) => {
-                if let Some(export_value) = map.get(subpath) {
-                    return self.resolve_export_value(info, export_value);
-                }
-            }
-            _ => {}
-        }
-        None
-    }
-
-    fn resolve_wildcard_export(
-        &self,
-        info: &PackageInfo,
-        exports: &Exports,
-        subpath: &str,
-    ) -> Option<PathBuf> {
-        if let Exports::Object(map) = exports {
-            for (pattern, value) in map {
-                // Handle patterns like "./*" or "./components/*"
-                if let Some(prefix) = pattern.strip_suffix('*') {
-                    let prefix = prefix.trim_start_matches("./");
-                    let full_subpath = format!("./{}", subpath);
-
-                    if let Some(suffix) = full_subpath
-                        .strip_prefix("./")
-                        .and_then(|s| s.strip_prefix(prefix))
-                    {
-                        // Found a matching wildcard pattern
-                        let resolved_path = match value {
-                            ExportValue::String(s) => {
-                                let target = s.replace('*', suffix);
-                                info.path.join(target.trim_start_matches("./"))
-                            }
-                            ExportValue::Conditional(conditions) => {
-                                // Prefer import > require > default
-                                let target = conditions
-                                    .get("import")
-                                    .or_else(|| conditions.get("require"))
-                                    .or_else(|| conditions.get("default"))?;
-                                let target = target.replace('*', suffix);
-                                info.path.join(target.trim_start_matches("./"))
-                            }
-                        };
-
-                        if resolved_path.exists() {
-                            debug!("Resolved via wildcard export: {:?}", resolved_path);
-                            return Some(resolved_path);
-                        }
-
-                        // Try adding extensions
-                        for ext in &[".ts", ".tsx", ".js", ".jsx"] {
-                            let with_ext =
-                                resolved_path.with_extension(ext.trim_start_matches('.'));
-                            if with_ext.exists() {
-                                debug!(
-                                    "Resolved via wildcard export with extension: {:?}",
-                                    with_ext
-                                );
-                                return Some(with_ext);
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        None
-    }
-
-    fn resolve_export_value(&self, info: &PackageInfo, value: &ExportValue) -> Option<PathBuf> {
-        let target = match value {
-            ExportValue::String(s) => s.clone(),
-            ExportValue::Conditional(conditions) => {
-                // Prefer import > require > default
-                conditions
-                    .get("import")
-                    .or_else(|| conditions.get("require"))
-                    .or_else(|| conditions.get("default"))?
-                    .clone()
-            }
-        };
-
-        let path = info.path.join(target.trim_start_matches("./"));
-        if path.exists() {
-            debug!("Resolved export value: {:?}", path);
-            return Some(path);
-        }
-
-        // Try adding extensions
-        for ext in &[".ts", ".tsx", ".js", ".jsx"] {
-            let with_ext = path.with_extension(ext.trim_start_matches('.'));
-            if with_ext.exists() {
-                debug!("Resolved export value with extension: {:?}", with_ext);
-                return Some(with_ext);
-            }
         }
 
         None
@@ -427,6 +899,10 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    fn default_conditions() -> Vec<String> {
+        DEFAULT_CONDITIONS.iter().map(|c| c.to_string()).collect()
+    }
+
     fn create_test_workspace() -> TempDir {
         let temp = TempDir::new().unwrap();
         let root = temp.path();
@@ -504,7 +980,7 @@ mod tests {
         let workspace = Workspace::detect(temp.path()).unwrap();
 
         // @test/ui should resolve to src/index.ts (fallback)
-        let resolved = workspace.resolve("@test/ui");
+        let resolved = workspace.resolve("@test/ui", &default_conditions());
         assert!(resolved.is_some());
         let path = resolved.unwrap();
         assert!(path.to_string_lossy().contains("index.ts"));
@@ -516,7 +992,7 @@ mod tests {
         let workspace = Workspace::detect(temp.path()).unwrap();
 
         // @test/utils should resolve via exports field
-        let resolved = workspace.resolve("@test/utils");
+        let resolved = workspace.resolve("@test/utils", &default_conditions());
         assert!(resolved.is_some());
         let path = resolved.unwrap();
         assert!(path.to_string_lossy().contains("src/index.ts"));
@@ -528,12 +1004,249 @@ mod tests {
         let workspace = Workspace::detect(temp.path()).unwrap();
 
         // @test/utils/helpers should resolve via exports field
-        let resolved = workspace.resolve("@test/utils/helpers");
+        let resolved = workspace.resolve("@test/utils/helpers", &default_conditions());
         assert!(resolved.is_some());
         let path = resolved.unwrap();
         assert!(path.to_string_lossy().contains("helpers.ts"));
     }
 
+    #[test]
+    fn test_package_imports_use_nearest_package_json() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join("src/internal")).unwrap();
+        fs::write(
+            root.join("package.json"),
+            r##"{
+                "name": "app",
+                "imports": {
+                    "#config": { "import": "./src/config.js", "default": "./src/config.cjs" },
+                    "#internal/*": "./src/internal/*.js",
+                    "#internal/db/*": "./src/db/*.js",
+                    "#polyfill": "core-js/stable"
+                }
+            }"##,
+        )
+        .unwrap();
+
+        let imports = PackageImports::default();
+        let importer = root.join("src/internal/a.ts");
+        assert_eq!(
+            imports.resolve(&importer, "#config", &default_conditions()),
+            vec![ImportsTarget::Path(root.join("./src/config.js"))]
+        );
+        assert_eq!(
+            imports.resolve(&importer, "#internal/cache", &default_conditions()),
+            vec![ImportsTarget::Path(root.join("./src/internal/cache.js"))]
+        );
+        // The longest prefix wins over `#internal/*`
+        assert_eq!(
+            imports.resolve(&importer, "#internal/db/pool", &default_conditions()),
+            vec![ImportsTarget::Path(root.join("./src/db/pool.js"))]
+        );
+        assert_eq!(
+            imports.resolve(&importer, "#polyfill", &default_conditions()),
+            vec![ImportsTarget::Package("core-js/stable".to_string())]
+        );
+        assert!(imports
+            .resolve(&importer, "#missing", &default_conditions())
+            .is_empty());
+    }
+
+    fn lookup(exports: serde_json::Value, key: &str, conditions: &[&str]) -> Option<Vec<String>> {
+        let conditions: Vec<String> = conditions.iter().map(|c| c.to_string()).collect();
+        match Workspace::parse_exports(exports)?.lookup(key, &conditions) {
+            ExportsMatch::Targets(targets) => Some(targets),
+            ExportsMatch::Excluded => Some(Vec::new()),
+            ExportsMatch::NoMatch => None,
+        }
+    }
+
+    #[test]
+    fn test_exports_nested_conditions_follow_declaration_order() {
+        let exports = serde_json::json!({
+            "types": "./dist/index.d.ts",
+            "development": { "import": "./src/index.ts" },
+            "default": "./dist/index.js"
+        });
+
+        assert_eq!(
+            lookup(exports.clone(), ".", &["import", "require"]),
+            Some(vec!["./dist/index.js".to_string()])
+        );
+        assert_eq!(
+            lookup(exports.clone(), ".", &["development", "import"]),
+            Some(vec!["./src/index.ts".to_string()])
+        );
+        // `development` matches but nothing inside it does, so `default` is used
+        assert_eq!(
+            lookup(exports, ".", &["development", "require"]),
+            Some(vec!["./dist/index.js".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_exports_arrays_and_null() {
+        let exports = serde_json::json!({
+            ".": [{ "worker": "./worker.js" }, "./index.js"],
+            "./*": "./src/*.ts",
+            "./internal/*": null,
+            "./internal/public/*": "./src/internal/public/*.ts"
+        });
+
+        assert_eq!(
+            lookup(exports.clone(), ".", &["import"]),
+            Some(vec!["./index.js".to_string()])
+        );
+        assert_eq!(
+            lookup(exports.clone(), ".", &["worker"]),
+            Some(vec!["./worker.js".to_string(), "./index.js".to_string()])
+        );
+        assert_eq!(
+            lookup(exports.clone(), "./internal/db", &["import"]),
+            Some(vec![])
+        );
+        // The most specific pattern wins, regardless of map order
+        assert_eq!(
+            lookup(exports.clone(), "./internal/public/api", &["import"]),
+            Some(vec!["./src/internal/public/api.ts".to_string()])
+        );
+        assert_eq!(
+            lookup(exports, "./button", &["import"]),
+            Some(vec!["./src/button.ts".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_null_export_is_not_resolved_through_fallbacks() {
+        let temp = create_test_workspace();
+        let utils_path = temp.path().join("packages/utils");
+        fs::write(
+            utils_path.join("package.json"),
+            r#"{
+                "name": "@test/utils",
+                "exports": {
+                    ".": "./src/index.ts",
+                    "./helpers": null
+                }
+            }"#,
+        )
+        .unwrap();
+        let workspace = Workspace::detect(temp.path()).unwrap();
+
+        assert!(workspace
+            .resolve("@test/utils/helpers", &default_conditions())
+            .is_none());
+        assert!(workspace
+            .resolve("@test/utils", &default_conditions())
+            .is_some());
+    }
+
+    #[test]
+    fn test_source_from_sourcemap() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join("lib")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/index.ts"), "export {};").unwrap();
+        fs::write(
+            root.join("lib/index.js.map"),
+            r#"{"version":3,"sourceRoot":"../src","sources":["webpack://pkg/runtime","missing.ts","index.ts"],"mappings":""}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            source_from_sourcemap(&root.join("lib/index.js")),
+            Some(root.join("src/index.ts"))
+        );
+        assert_eq!(source_from_sourcemap(&root.join("lib/other.js")), None);
+    }
+
+    fn write_package(dir: &Path, name: &str) {
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(
+            dir.join("package.json"),
+            format!(r#"{{ "name": "{}" }}"#, name),
+        )
+        .unwrap();
+        fs::write(dir.join("src/index.ts"), "export {};").unwrap();
+    }
+
+    #[test]
+    fn test_pnpm_negation_patterns() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::write(
+            root.join("pnpm-workspace.yaml"),
+            "packages:\n  - 'packages/**'\n  - '!packages/legacy/**'\n",
+        )
+        .unwrap();
+        write_package(&root.join("packages/web"), "web");
+        write_package(&root.join("packages/legacy"), "legacy");
+        write_package(&root.join("packages/legacy/old-ui"), "old-ui");
+        write_package(&root.join("packages/web/node_modules/dep"), "dep");
+
+        let workspace = Workspace::detect(root).unwrap();
+        assert_eq!(workspace.format, WorkspaceFormat::Pnpm);
+        assert_eq!(workspace.packages.keys().collect::<Vec<_>>(), vec!["web"],);
+    }
+
+    #[test]
+    fn test_lerna_and_rush_workspaces() {
+        let temp = TempDir::new().unwrap();
+        let lerna = temp.path().join("lerna");
+        fs::create_dir_all(&lerna).unwrap();
+        fs::write(lerna.join("lerna.json"), r#"{ "packages": ["modules/*"] }"#).unwrap();
+        write_package(&lerna.join("modules/core"), "@lerna/core");
+
+        let workspace = Workspace::detect(&lerna).unwrap();
+        assert_eq!(workspace.format, WorkspaceFormat::Lerna);
+        assert!(workspace.packages.contains_key("@lerna/core"));
+
+        let rush = temp.path().join("rush");
+        fs::create_dir_all(&rush).unwrap();
+        fs::write(
+            rush.join("rush.json"),
+            r#"{
+                // rush.json allows comments
+                "rushVersion": "5.0.0",
+                "projects": [
+                    { "packageName": "@rush/app", "projectFolder": "apps/app" },
+                ]
+            }"#,
+        )
+        .unwrap();
+        write_package(&rush.join("apps/app"), "@rush/app");
+
+        let workspace = Workspace::detect(&rush).unwrap();
+        assert_eq!(workspace.format, WorkspaceFormat::Rush);
+        assert!(workspace.packages.contains_key("@rush/app"));
+    }
+
+    #[test]
+    fn test_nx_project_json() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::write(root.join("nx.json"), "{}").unwrap();
+        fs::create_dir_all(root.join("libs/shared/src")).unwrap();
+        fs::write(
+            root.join("libs/shared/project.json"),
+            r#"{ "name": "shared" }"#,
+        )
+        .unwrap();
+        fs::write(root.join("libs/shared/src/index.ts"), "export {};").unwrap();
+        write_package(&root.join("apps/web"), "@nx/web");
+        fs::write(root.join("apps/web/project.json"), r#"{ "name": "web" }"#).unwrap();
+
+        let workspace = Workspace::detect(root).unwrap();
+        assert_eq!(workspace.format, WorkspaceFormat::Nx);
+        // package.json names win over Nx project names
+        assert!(workspace.packages.contains_key("@nx/web"));
+        assert!(workspace
+            .resolve("shared", &default_conditions())
+            .is_some_and(|path| path.ends_with("libs/shared/src/index.ts")));
+    }
+
     #[test]
     fn test_no_workspace() {
         let temp = TempDir::new().unwrap();
@@ -207,7 +207,7 @@ fn test_type_only_cycle_ignored_with_flag() {
 
 #[test]
 fn test_ignore_type_imports_reduces_cycle_count() {
-    // Full monorepo source: 5 cycles without flag, 4 with flag
+    // Full monorepo source: 5 cycles without flag, 3 with flag (one is `import type`, one elided)
     cdd()
         .args([
             "-n",
@@ -223,7 +223,7 @@ fn test_ignore_type_imports_reduces_cycle_count() {
         .args([
             "--ignore-type-imports",
             "-n",
-            "4",
+            "3",
             "--exclude",
             "dist",
             "./fixtures/example-monorepo/packages",
@@ -487,3 +487,691 @@ fn test_complex_workspace_resolves_subpath_imports() {
         .success()
         .stderr(predicate::str::contains("22 files")); // Should collect all 22 files
 }
+
+// ============ Single-file component tests ============
+
+#[test]
+fn test_sfc_cycles_detected() {
+    // Vue: Parent.vue -> Child.vue -> useParent.ts -> Parent.vue
+    // Svelte: Widget.svelte -> widgetStore.ts -> Widget.svelte
+    cdd()
+        .args(["-n", "2", "./fixtures/sfc-components"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Collected 6 files"))
+        .stderr(predicate::str::contains("Child.vue:6"))
+        .stderr(predicate::str::contains("Widget.svelte:2"));
+}
+
+#[test]
+fn test_sfc_type_only_cycle_ignored_with_flag() {
+    // widgetStore.ts only imports the Svelte component as a type
+    cdd()
+        .args([
+            "--ignore-type-imports",
+            "-n",
+            "1",
+            "./fixtures/sfc-components",
+        ])
+        .assert()
+        .success();
+}
+
+// ============ ESM/CJS TypeScript and declaration file tests ============
+
+#[test]
+fn test_mts_cts_files_resolved() {
+    // a.mts -> ./b.mjs (b.mts) -> ./c.cjs (c.cts), plus a.mts <-> shapes.d.ts
+    cdd()
+        .args(["-n", "1", "./fixtures/ts-module-formats"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Collected 4 files"))
+        .stderr(predicate::str::contains("4 nodes and 4 edges"))
+        .stderr(predicate::str::contains("shapes.d.ts"));
+}
+
+#[test]
+fn test_declaration_file_edges_ignored_with_flag() {
+    // Edges from shapes.d.ts are type-only, so the cycle disappears
+    cdd()
+        .args([
+            "--ignore-type-imports",
+            "-n",
+            "0",
+            "./fixtures/ts-module-formats",
+        ])
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_import_equals_and_triple_slash_references() {
+    // `path="settings.ts"` is relative to the file even without `./`
+    cdd()
+        .args(["-n", "2", "./fixtures/ts-references"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "| /// <reference path=\"settings.ts\" />",
+        ))
+        .stderr(predicate::str::contains("| import api = require('./api');"));
+
+    // References are type-only, so only the import-equals cycle remains.
+    // `api.ts` uses `export =`, and the edge into it still resolves.
+    cdd()
+        .args(["-t", "-n", "1", "./fixtures/ts-references"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("src/legacy/store.ts:1"))
+        .stderr(predicate::str::contains("| import api = require('./api');"))
+        .stderr(predicate::str::contains("src/legacy/api.ts:1"));
+}
+
+// ============ Imported specifier tests ============
+
+#[test]
+fn test_cycle_edges_show_imported_bindings() {
+    cdd()
+        .args(["./fixtures/example-monorepo/packages/api"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("uses: UserService"))
+        .stderr(predicate::str::contains("uses: OrderService"));
+}
+
+#[test]
+fn test_json_output_includes_specifiers() {
+    cdd()
+        .args(["--json", "./fixtures/example-monorepo/packages/api"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(r#""specifiers""#))
+        .stdout(predicate::str::contains(r#""imported": "UserService""#));
+}
+
+// ============ Eager/deferred edge tests ============
+
+#[test]
+fn test_deferred_edges_are_marked() {
+    // base.ts only uses Derived inside a method, so its edge is deferred
+    cdd()
+        .args(["-n", "2", "./fixtures/eager-cycles"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("base.ts:1 (deferred)"));
+}
+
+#[test]
+fn test_eager_only_reports_runtime_hazards() {
+    // Only config.ts <-> defaults.ts reads bindings during evaluation on both sides
+    cdd()
+        .args(["--eager-only", "-n", "1", "./fixtures/eager-cycles"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("config.ts"))
+        .stderr(predicate::str::contains("base.ts").not());
+}
+
+// ============ Elided import tests ============
+
+#[test]
+fn test_type_usage_import_counts_by_default() {
+    // session.ts imports `User` without `import type`, which is still an edge without -t
+    cdd()
+        .args(["-n", "1", "./fixtures/elided-imports"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_ignore_type_imports_drops_elided_imports() {
+    cdd()
+        .args(["-t", "-n", "0", "./fixtures/elided-imports"])
+        .assert()
+        .success();
+}
+
+// ============ Glob import tests ============
+
+#[test]
+fn test_glob_imports_create_edges() {
+    // router.ts <-> pages via import.meta.glob, legacy/index.js <-> modules/a.js via require.context
+    cdd()
+        .args(["-n", "2", "./fixtures/glob-imports"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("import.meta.glob("))
+        .stderr(predicate::str::contains("require.context("))
+        .stderr(predicate::str::contains("modules/a.js"))
+        .stderr(predicate::str::contains("Home.test.tsx").not())
+        .stderr(predicate::str::contains("modules/c.ts").not());
+}
+
+#[test]
+fn test_unexpanded_glob_patterns_are_reported() {
+    // widgets.ts matches pages with nested braces; its root-relative pattern is listed
+    cdd()
+        .args(["-n", "2", "--fail-on-unresolved", "./fixtures/glob-imports"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("9 nodes and 11 edges"))
+        .stderr(predicate::str::contains(
+            "src/widgets.ts:2 '/src/plugins/*.ts' (glob pattern)",
+        ));
+}
+
+// ============ Computed specifier tests ============
+
+#[test]
+fn test_computed_specifiers_are_speculative() {
+    // i18n.ts <-> locales/en.ts via a template literal, dispatch.js <-> handlers/ping.js via concatenation
+    cdd()
+        .args(["-n", "2", "./fixtures/dynamic-specifiers"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "i18n.ts:4 (deferred) (speculative)",
+        ))
+        .stderr(predicate::str::contains(
+            "dispatch.js:2 (deferred) (speculative)",
+        ));
+}
+
+#[test]
+fn test_json_output_marks_speculative_edges() {
+    cdd()
+        .args(["--json", "-n", "2", "./fixtures/dynamic-specifiers"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""is_speculative": true"#));
+}
+
+// ============ Mock edge tests ============
+
+#[test]
+fn test_mock_cycles_reported_separately_by_default() {
+    // registry.ts <-> testing.ts only exists through vi.mock(), so it isn't counted
+    cdd()
+        .args(["-n", "1", "./fixtures/mock-cycles"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("through test mocks (not counted)"))
+        .stderr(predicate::str::contains("vi.mock('./registry')"));
+}
+
+#[test]
+fn test_mock_imports_count_and_exclude() {
+    cdd()
+        .args([
+            "--mock-imports",
+            "count",
+            "-n",
+            "2",
+            "./fixtures/mock-cycles",
+        ])
+        .assert()
+        .success();
+
+    cdd()
+        .args([
+            "--mock-imports",
+            "exclude",
+            "-n",
+            "1",
+            "./fixtures/mock-cycles",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("test mocks").not());
+}
+
+#[test]
+fn test_json_output_lists_mock_cycles() {
+    cdd()
+        .args(["--json", "-n", "1", "./fixtures/mock-cycles"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""total_cycles": 1"#))
+        .stdout(predicate::str::contains(r#""mock_cycles""#))
+        .stdout(predicate::str::contains(r#""kind": "Mock""#));
+}
+
+// ============ Worker edge tests ============
+
+#[test]
+fn test_worker_edges_excluded_by_default() {
+    // main.ts -> worker.ts is a worker edge, so the cycle back to main.ts isn't counted
+    cdd()
+        .args(["./fixtures/worker-urls"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("3 nodes and 3 edges"));
+}
+
+#[test]
+fn test_include_workers() {
+    cdd()
+        .args(["--include-workers", "-n", "1", "./fixtures/worker-urls"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("new Worker(new URL('./worker.ts'"));
+}
+
+// ============ tsconfig parsing tests ============
+
+#[test]
+fn test_jsonc_tsconfig_aliases_resolve() {
+    // tsconfig.json has comments and trailing commas; the cycle only exists through `@/*`
+    cdd()
+        .args(["-n", "1", "./fixtures/jsonc-tsconfig"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Failed to parse tsconfig").not());
+}
+
+#[test]
+fn test_nearest_tsconfig_per_package() {
+    // web and api map `@/*` to different directories in their own tsconfig.json
+    cdd()
+        .args(["-n", "2", "./fixtures/nested-tsconfig"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("web/src/theme.ts"))
+        .stderr(predicate::str::contains("api/lib/db.ts"));
+}
+
+#[test]
+fn test_project_references() {
+    // app -> core resolves via core's `paths` and by mapping core's outDir back to its rootDir
+    cdd()
+        .args(["-n", "2", "./fixtures/project-references"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("packages/core/src/format.ts"))
+        .stderr(predicate::str::contains("packages/core/src/store.ts"));
+}
+
+#[test]
+fn test_overlapping_paths_fall_through_replacements() {
+    // `@/components/*` wins over `@/*`; its first replacement (src/ui) doesn't exist
+    for _ in 0..5 {
+        cdd()
+            .args(["-n", "1", "./fixtures/overlapping-paths"])
+            .assert()
+            .success();
+    }
+}
+
+#[test]
+fn test_tsconfig_extends_package_and_config_dir() {
+    // `paths` come from a package in the parent node_modules and use `${configDir}`
+    cdd()
+        .args(["-n", "1", "./fixtures/tsconfig-extends/app"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("src/orders.ts"));
+}
+
+#[test]
+fn test_root_dirs_merge_generated_code() {
+    // generated/api/schema.ts imports './client', which lives in src/api via rootDirs
+    cdd()
+        .args(["-n", "1", "./fixtures/root-dirs"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("generated/api/schema.ts"));
+}
+
+#[test]
+fn test_module_suffixes_resolve_first_variant() {
+    // `./storage` resolves to storage.ios.ts only, like tsc
+    cdd()
+        .args(["-n", "1", "./fixtures/module-suffixes"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("src/storage.ios.ts"))
+        .stderr(predicate::str::contains("storage.native.ts").not());
+}
+
+#[test]
+fn test_module_suffixes_all_variants() {
+    // `./storage` gets an edge to each of storage.ios.ts, storage.native.ts and storage.ts
+    cdd()
+        .args([
+            "-n",
+            "1",
+            "--module-suffixes",
+            "all",
+            "./fixtures/module-suffixes",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("4 nodes and 5 edges"));
+}
+
+#[test]
+fn test_module_suffixes_target_platform() {
+    // Android has no `.ios` variant, so `./storage` resolves to storage.native.ts
+    cdd()
+        .args([
+            "-n",
+            "1",
+            "--platform-suffixes",
+            ".android,.native,",
+            "./fixtures/module-suffixes",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("src/storage.native.ts"))
+        .stderr(predicate::str::contains("storage.ios.ts").not());
+
+    // The web build uses the plain storage.ts, which has no cycle
+    cdd()
+        .args([
+            "-n",
+            "0",
+            "--platform-suffixes",
+            ".web,",
+            "./fixtures/module-suffixes",
+        ])
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_module_suffixes_reject_unknown_values() {
+    cdd()
+        .args(["--module-suffixes", "al", "./fixtures/module-suffixes"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value 'al'"));
+
+    cdd()
+        .args([
+            "--platform-suffixes",
+            "android,",
+            "./fixtures/module-suffixes",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid module suffix 'android'"));
+}
+
+#[test]
+fn test_package_json_subpath_imports() {
+    // '#config' and '#internal/*' resolve through the package.json `imports` field
+    cdd()
+        .args(["-n", "1", "./fixtures/subpath-imports"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("src/internal/db.ts"));
+}
+
+#[test]
+fn test_conditional_exports_default_condition() {
+    // Without `development`, @acme/core resolves to its prebuilt dist/index.js
+    cdd()
+        .args(["-n", "0", "./fixtures/conditional-exports"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_conditional_exports_custom_conditions() {
+    // `development` selects the nested `import` condition, pointing at the source
+    cdd()
+        .args([
+            "-n",
+            "1",
+            "--conditions",
+            "development,browser,import",
+            "./fixtures/conditional-exports",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("packages/core/src/index.ts"));
+}
+
+#[test]
+fn test_built_entry_points_map_to_sources() {
+    // main/module/exports point at dist/ (via tsconfig outDir) or lib/ (via sourcemap)
+    cdd()
+        .args(["-n", "2", "./fixtures/dist-entry-points"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("packages/ui/src/index.ts"))
+        .stderr(predicate::str::contains("packages/icons/source/index.ts"));
+}
+
+#[test]
+fn test_workspace_root_found_from_subdirectory() {
+    // Scanning one package still resolves @test/ui against the enclosing workspace
+    cdd()
+        .args(["./fixtures/workspace-monorepo/packages/core/src"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Detected workspace with 3 packages",
+        ))
+        .stderr(predicate::str::contains(
+            "1 imports point outside the scanned directory",
+        ))
+        .stderr(predicate::str::contains(
+            "index.ts:2 -> ../../ui/src/index.ts",
+        ));
+}
+
+#[test]
+fn test_boundary_edges_in_json_output() {
+    let output = cdd()
+        .args(["--json", "./fixtures/workspace-monorepo/packages/core/src"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert_eq!(json["total_cycles"], 0);
+    assert_eq!(json["boundary_edges"][0]["from_file"], "index.ts");
+    assert_eq!(
+        json["boundary_edges"][0]["to_file"],
+        "../../ui/src/index.ts"
+    );
+}
+
+#[test]
+fn test_pnpm_negation_excludes_packages() {
+    // `!packages/legacy` removes @demo/legacy, so the app -> legacy import isn't resolved
+    cdd()
+        .args(["-n", "0", "./fixtures/pnpm-negation"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Detected workspace with 1 packages (pnpm-workspace.yaml): @demo/app",
+        ));
+}
+
+#[test]
+fn test_json_output_includes_workspace_format() {
+    cdd()
+        .args(["--json", "-n", "0", "./fixtures/pnpm-negation"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""workspace_format": "pnpm""#));
+
+    cdd()
+        .args([
+            "--json",
+            "--no-workspace",
+            "-n",
+            "0",
+            "./fixtures/pnpm-negation",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("workspace_format").not());
+}
+
+#[test]
+fn test_symlinked_packages_resolve_through_node_modules() {
+    // No `workspaces` field: @acme/web and @acme/ui only reach each other via node_modules links
+    cdd()
+        .args(["-n", "1", "./fixtures/linked-packages"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("apps/web/src/main.ts:1"))
+        .stderr(predicate::str::contains("packages/ui/src/index.ts:1"))
+        .stderr(predicate::str::contains("external package").not());
+}
+
+#[test]
+fn test_external_nodes_json() {
+    let output = cdd()
+        .args([
+            "--json",
+            "--external-nodes",
+            "-n",
+            "1",
+            "./fixtures/linked-packages",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["total_cycles"], 1);
+    assert_eq!(json["external_packages"], serde_json::json!(["lodash"]));
+}
+
+#[test]
+fn test_external_nodes_per_installed_copy() {
+    // apps/web uses the hoisted lodash 4, packages/ui its own lodash 3
+    cdd()
+        .args(["--external-nodes", "-n", "1", "./fixtures/linked-packages"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("6 nodes and 4 edges"))
+        .stderr(predicate::str::contains(
+            "Recorded 1 external package(s): lodash",
+        ));
+}
+
+#[test]
+fn test_bundler_and_config_aliases() {
+    // `@` comes from vite.config.ts, `~shared` from .cddrc.json and `@admin`
+    // from the webpack config nearest to apps/admin
+    cdd()
+        .args(["-n", "2", "./fixtures/bundler-aliases"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("shared/c.ts:1"))
+        .stderr(predicate::str::contains("apps/admin/src/x.js:1"));
+}
+
+#[test]
+fn test_resolver_chain_from_cli() {
+    // Without the workspace resolver, cross-package imports aren't resolved
+    cdd()
+        .args([
+            "--resolvers",
+            "relative,tsconfig",
+            "-n",
+            "0",
+            "./fixtures/workspace-monorepo",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("no circular dependencies found"));
+}
+
+#[test]
+fn test_json_edges_record_resolver() {
+    let output = cdd()
+        .args(["--json", "-n", "1", "./fixtures/workspace-monorepo"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let edges = json["cycles"][0]["edges"].as_array().unwrap();
+    assert!(!edges.is_empty());
+    assert!(edges.iter().all(|edge| edge["resolver"] == "workspace"));
+}
+
+#[test]
+fn test_unresolved_imports_reported() {
+    // `./UserServce` is misspelled and `@/helpers/dates` doesn't exist; `react`
+    // and `node:fs` are external on purpose
+    let output = cdd()
+        .args(["--json", "./fixtures/unresolved-imports"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let unresolved = json["unresolved_imports"].as_array().unwrap();
+    assert_eq!(unresolved.len(), 2);
+    assert_eq!(unresolved[0]["from_file"], "src/app.ts");
+    assert_eq!(unresolved[0]["line"], 4);
+    assert_eq!(unresolved[0]["specifier"], "@/helpers/dates");
+    assert_eq!(unresolved[0]["kind"], "alias");
+    assert_eq!(unresolved[1]["from_file"], "src/users/UserRepository.ts");
+    assert_eq!(unresolved[1]["specifier"], "./UserServce");
+    assert_eq!(unresolved[1]["kind"], "relative");
+}
+
+#[test]
+fn test_fail_on_unresolved() {
+    cdd()
+        .args(["./fixtures/unresolved-imports"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "src/users/UserRepository.ts:1 './UserServce'",
+        ));
+
+    cdd()
+        .args(["--fail-on-unresolved", "./fixtures/unresolved-imports"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "2 relative or aliased import(s) don't resolve",
+        ));
+}
+
+#[test]
+fn test_external_nodes_need_node_modules_resolver() {
+    let output = cdd()
+        .args([
+            "--json",
+            "--external-nodes",
+            "--resolvers",
+            "relative,workspace",
+            "./fixtures/linked-packages",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json.get("external_packages").is_none());
+}
+
+#[test]
+fn test_asset_queries_resolve() {
+    // `#hash` and `?worker` suffixes are dropped before resolving; the `?raw`
+    // import of snippet.ts doesn't evaluate it, so it has no edge
+    cdd()
+        .args(["--fail-on-unresolved", "./fixtures/asset-queries"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("3 nodes and 3 edges"))
+        .stderr(predicate::str::contains("don't resolve").not());
+
+    // `?worker` is a worker edge, so it only closes a cycle with --include-workers
+    cdd()
+        .args(["--include-workers", "-n", "1", "./fixtures/asset-queries"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "| import ResizeWorker from './resize.ts?worker';",
+        ));
+}
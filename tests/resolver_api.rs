@@ -0,0 +1,74 @@
+use cdd::{
+    build_dependency_graph, ParserOptions, ResolveContext, ResolveOptions, ResolveRequest, Resolver,
+};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+/// Maps `virtual:<name>` to `generated/<name>.ts` next to the importer, like a
+/// code generator's bundler plugin would.
+#[derive(Debug)]
+struct GeneratedResolver;
+
+impl Resolver for GeneratedResolver {
+    fn name(&self) -> &'static str {
+        "generated"
+    }
+
+    fn resolve(&self, ctx: &ResolveContext, request: &ResolveRequest) -> Vec<PathBuf> {
+        let Some(name) = request.specifier.strip_prefix("virtual:") else {
+            return Vec::new();
+        };
+        let Some(dir) = request.importer.parent() else {
+            return Vec::new();
+        };
+        ctx.probe(request, dir.join("generated").join(name))
+    }
+}
+
+#[test]
+fn test_resolver_registered_through_library() {
+    let temp = TempDir::new().unwrap();
+    let root = temp.path().canonicalize().unwrap();
+    fs::create_dir_all(root.join("generated")).unwrap();
+    fs::write(
+        root.join("app.ts"),
+        "import { routes } from 'virtual:routes';\nexport const app = routes;\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("generated/routes.ts"),
+        "import { app } from '../app';\nexport const routes = [app];\n",
+    )
+    .unwrap();
+    let files = vec![root.join("app.ts"), root.join("generated/routes.ts")];
+
+    // The built-in chain can't resolve `virtual:routes`
+    let graph = build_dependency_graph(
+        &files,
+        &root,
+        &ParserOptions::default(),
+        &ResolveOptions::default(),
+        None,
+        None,
+    );
+    assert_eq!(graph.graph.edge_count(), 1);
+    assert_eq!(graph.unresolved_imports.len(), 0);
+
+    let mut options = ResolveOptions::default();
+    options.resolvers.push(Arc::new(GeneratedResolver));
+    let graph = build_dependency_graph(
+        &files,
+        &root,
+        &ParserOptions::default(),
+        &options,
+        None,
+        None,
+    );
+    assert_eq!(graph.graph.edge_count(), 2);
+    assert!(graph
+        .graph
+        .edge_weights()
+        .any(|edge| edge.resolver == "generated" && edge.import.source == "virtual:routes"));
+}